@@ -1,79 +1,139 @@
+pub mod audio;
 pub mod browser;
+pub mod clock;
 pub mod event;
+pub mod font;
+pub mod gamepad;
 pub mod geometry;
+pub mod image;
+pub mod input;
+pub mod mask;
+pub mod random;
 pub mod renderer;
+pub mod replay;
+pub mod scheduler;
 pub mod sequence;
+pub mod settings;
 pub mod sprite;
 pub mod ui;
 
-use anyhow::anyhow;
 use anyhow::Result;
 use async_trait::async_trait;
-use std::cell::RefCell;
-use std::rc::Rc;
 
+use self::clock::{BrowserClock, Clock};
 use self::event::{Event, EventSource};
+use self::input::InputState;
 use self::renderer::Renderer;
+use self::scheduler::{AnimationFrameScheduler, FrameScheduler};
 
-type SharedLoopClosure = Rc<RefCell<Option<browser::LoopClosure>>>;
+pub type DrawFn = dyn Fn(&dyn Renderer);
 
-pub struct DrawCommand(pub u8, pub Box<dyn Fn(&dyn Renderer)>);
+pub struct DrawCommand(pub u8, pub Box<DrawFn>);
 
 #[async_trait(?Send)]
 pub trait Game {
     async fn initialize(&mut self) -> Result<()>;
-    fn update(&mut self, delta: f32, events: &[Event]) -> Result<()>;
-    fn draw(&self) -> Vec<DrawCommand>;
+    fn update(&mut self, delta: f32, input: &InputState, events: &[Event]) -> Result<()>;
+    fn draw(&self, alpha: f32) -> Vec<DrawCommand>;
+}
+
+#[derive(Clone, Copy)]
+pub struct FixedTimestep {
+    pub tick_rate: f32,
+    pub max_steps: u32,
+}
+
+impl FixedTimestep {
+    pub fn new(tick_rate: f32, max_steps: u32) -> Self {
+        Self {
+            tick_rate,
+            max_steps,
+        }
+    }
+
+    pub fn step_ms(&self) -> f64 {
+        1000.0 / self.tick_rate as f64
+    }
 }
 
 pub struct GameLoop<G: Game + 'static, R: renderer::Renderer + 'static, E: EventSource + 'static> {
     game: G,
     renderer: R,
     event_source: E,
+    input: InputState,
     last_frame: f64,
+    frame: u64,
+    elapsed: f64,
+    fixed_timestep: Option<FixedTimestep>,
+    max_frame_delta: Option<f64>,
+    accumulator: f64,
+    alpha: f32,
 }
 
 impl<G: Game + 'static, R: Renderer + 'static, E: EventSource + 'static> GameLoop<G, R, E> {
-    fn new(game: G, renderer: R, event_source: E, last_frame: f64) -> Self {
+    pub fn new(game: G, renderer: R, event_source: E) -> Self {
         Self {
             game,
             renderer,
             event_source,
-            last_frame,
+            input: InputState::new(),
+            last_frame: 0.0,
+            frame: 0,
+            elapsed: 0.0,
+            fixed_timestep: None,
+            max_frame_delta: None,
+            accumulator: 0.0,
+            alpha: 1.0,
         }
     }
 
-    pub async fn start(game: G, renderer: R, event_source: E) -> Result<()> {
-        let mut game_loop = {
-            let last_frame = browser::now()?;
-            Self::new(game, renderer, event_source, last_frame)
-        };
+    pub fn with_fixed_timestep(mut self, fixed_timestep: FixedTimestep) -> Self {
+        self.fixed_timestep = Some(fixed_timestep);
+        self
+    }
+
+    // Browsers stop calling back while the tab is hidden, so the first frame
+    // after returning can be seconds long. Longer frames are cut down to this.
+    pub fn with_max_frame_delta(mut self, max_frame_delta: f64) -> Self {
+        self.max_frame_delta = Some(max_frame_delta);
+        self
+    }
 
-        game_loop.initialize().await?;
+    pub async fn start(self) -> Result<()> {
+        self.run(&BrowserClock, &AnimationFrameScheduler).await
+    }
 
-        let f: SharedLoopClosure = Rc::new(RefCell::new(None));
-        let g = f.clone();
-        *g.borrow_mut() = Some(browser::create_raf_closure(move |perf: f64| {
-            let events = game_loop.collect_events();
-            game_loop.update(perf, &events).expect("Failed to update");
-            game_loop.render();
+    pub async fn run(mut self, clock: &impl Clock, scheduler: &impl FrameScheduler) -> Result<()> {
+        self.initialize(clock).await?;
+        scheduler.start(Box::new(move |timestamp: f64| self.step(timestamp)))
+    }
 
-            browser::request_animation_frame(f.borrow().as_ref().unwrap()).expect("Loop failed");
-        }));
+    pub async fn initialize(&mut self, clock: &impl Clock) -> Result<()> {
+        self.game.initialize().await?;
+        self.last_frame = clock.now()?;
+        Ok(())
+    }
 
-        browser::request_animation_frame(
-            g.borrow()
-                .as_ref()
-                .ok_or_else(|| anyhow!("GameLoop: Loop is None"))?,
-        )?;
+    pub fn step(&mut self, timestamp: f64) -> Result<()> {
+        match self.fixed_timestep {
+            Some(fixed_timestep) => self.update_fixed(timestamp, fixed_timestep)?,
+            None => self.update(timestamp)?,
+        }
+        self.render();
         Ok(())
     }
 
-    async fn initialize(&mut self) -> Result<()> {
-        self.game.initialize().await
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    pub fn renderer(&self) -> &R {
+        &self.renderer
     }
 
     fn collect_events(&mut self) -> Vec<Event> {
+        self.event_source.begin_update(self.frame, self.elapsed);
+
         let mut events = vec![];
         while let Some(evt) = self.event_source.try_next() {
             events.push(evt);
@@ -81,17 +141,58 @@ impl<G: Game + 'static, R: Renderer + 'static, E: EventSource + 'static> GameLoo
         events
     }
 
-    fn update(&mut self, perf: f64, events: &[Event]) -> Result<()> {
-        let delta = (perf - self.last_frame) as f32;
+    fn frame_delta(&self, perf: f64) -> f64 {
+        let delta = perf - self.last_frame;
+        match self.max_frame_delta {
+            Some(max_frame_delta) => delta.min(max_frame_delta),
+            None => delta,
+        }
+    }
 
-        self.game.update(delta, events)?;
+    fn update(&mut self, perf: f64) -> Result<()> {
+        let delta = self.frame_delta(perf);
+
+        self.tick(delta)?;
 
         self.last_frame = perf;
         Ok(())
     }
 
+    fn tick(&mut self, delta: f64) -> Result<()> {
+        let events = self.collect_events();
+        let delta = self.event_source.update_delta(delta);
+        self.input.update(&events);
+        self.game.update(delta as f32, &self.input, &events)?;
+
+        self.frame += 1;
+        self.elapsed += delta;
+        Ok(())
+    }
+
+    fn update_fixed(&mut self, perf: f64, fixed_timestep: FixedTimestep) -> Result<()> {
+        let step_ms = fixed_timestep.step_ms();
+        self.accumulator += self.frame_delta(perf);
+        self.last_frame = perf;
+
+        let mut steps = 0;
+        while step_ms <= self.accumulator {
+            if fixed_timestep.max_steps <= steps {
+                // Too far behind to catch up; drop the backlog instead of spiralling.
+                self.accumulator %= step_ms;
+                break;
+            }
+
+            self.tick(step_ms)?;
+            self.accumulator -= step_ms;
+            steps += 1;
+        }
+
+        self.alpha = (self.accumulator / step_ms) as f32;
+        Ok(())
+    }
+
     fn render(&self) {
-        let mut drawables = self.game.draw();
+        let mut drawables = self.game.draw(self.alpha);
         drawables.sort_by_key(|d| d.0);
         drawables.iter().for_each(|d| d.1(&self.renderer));
     }
@@ -0,0 +1,255 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use js_sys::ArrayBuffer;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AudioBuffer, AudioBufferSourceNode, AudioContext, AudioScheduledSourceNode};
+
+use self::sfx::PcmBuffer;
+use super::browser;
+
+pub mod sequencer;
+pub mod sfx;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Sound(usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Playback(usize);
+
+#[async_trait(?Send)]
+pub trait AudioBackend {
+    async fn load(&self, path: &str) -> Result<Sound>;
+    async fn decode(&self, bytes: &[u8]) -> Result<Sound>;
+    fn upload(&self, buffer: &PcmBuffer) -> Result<Sound>;
+    fn play(&self, sound: Sound) -> Result<()>;
+    fn play_looping(&self, sound: Sound) -> Result<Playback>;
+    fn stop(&self, playback: Playback) -> Result<()>;
+}
+
+pub struct WebAudioBackend {
+    context: AudioContext,
+    buffers: RefCell<Vec<AudioBuffer>>,
+    loops: RefCell<HashMap<Playback, AudioBufferSourceNode>>,
+    next_playback: Cell<usize>,
+}
+
+impl WebAudioBackend {
+    pub fn new() -> Result<Self> {
+        let context = AudioContext::new()
+            .map_err(|err| anyhow!("Could not create AudioContext {:#?}", err))?;
+
+        Ok(Self {
+            context,
+            buffers: RefCell::new(vec![]),
+            loops: RefCell::new(HashMap::new()),
+            next_playback: Cell::new(0),
+        })
+    }
+
+    async fn decode_array_buffer(&self, array_buffer: &ArrayBuffer) -> Result<Sound> {
+        let promise = self
+            .context
+            .decode_audio_data(array_buffer)
+            .map_err(|err| anyhow!("Could not decode audio {:#?}", err))?;
+        let buffer: AudioBuffer = JsFuture::from(promise)
+            .await
+            .map_err(|err| anyhow!("Could not decode audio {:#?}", err))?
+            .dyn_into()
+            .map_err(|element| anyhow!("Error converting {:#?} to AudioBuffer", element))?;
+
+        Ok(self.push_buffer(buffer))
+    }
+
+    fn push_buffer(&self, buffer: AudioBuffer) -> Sound {
+        let mut buffers = self.buffers.borrow_mut();
+        buffers.push(buffer);
+        Sound(buffers.len() - 1)
+    }
+
+    fn start_source(&self, sound: Sound, looping: bool) -> Result<AudioBufferSourceNode> {
+        // Browsers keep the context suspended until the first user gesture.
+        let _ = self.context.resume();
+
+        let buffers = self.buffers.borrow();
+        let buffer = buffers
+            .get(sound.0)
+            .ok_or_else(|| anyhow!("Unknown sound {:?}", sound))?;
+        let source = self
+            .context
+            .create_buffer_source()
+            .map_err(|err| anyhow!("Could not create buffer source {:#?}", err))?;
+        source.set_buffer(Some(buffer));
+        source.set_loop(looping);
+        source
+            .connect_with_audio_node(&self.context.destination())
+            .map_err(|err| anyhow!("Could not connect buffer source {:#?}", err))?;
+        source
+            .start()
+            .map_err(|err| anyhow!("Could not start buffer source {:#?}", err))?;
+        Ok(source)
+    }
+}
+
+#[async_trait(?Send)]
+impl AudioBackend for WebAudioBackend {
+    async fn load(&self, path: &str) -> Result<Sound> {
+        let array_buffer = browser::fetch_array_buffer(path).await?;
+        self.decode_array_buffer(&array_buffer).await
+    }
+
+    async fn decode(&self, bytes: &[u8]) -> Result<Sound> {
+        let array_buffer = js_sys::Uint8Array::from(bytes).buffer();
+        self.decode_array_buffer(&array_buffer).await
+    }
+
+    fn upload(&self, buffer: &PcmBuffer) -> Result<Sound> {
+        let audio_buffer = self
+            .context
+            .create_buffer(
+                1,
+                buffer.samples().len() as u32,
+                buffer.sample_rate() as f32,
+            )
+            .map_err(|err| anyhow!("Could not create AudioBuffer {:#?}", err))?;
+        audio_buffer
+            .copy_to_channel(buffer.samples(), 0)
+            .map_err(|err| anyhow!("Could not copy samples to AudioBuffer {:#?}", err))?;
+        Ok(self.push_buffer(audio_buffer))
+    }
+
+    fn play(&self, sound: Sound) -> Result<()> {
+        self.start_source(sound, false)?;
+        Ok(())
+    }
+
+    fn play_looping(&self, sound: Sound) -> Result<Playback> {
+        let source = self.start_source(sound, true)?;
+        let playback = Playback(self.next_playback.get());
+        self.next_playback.set(playback.0 + 1);
+        self.loops.borrow_mut().insert(playback, source);
+        Ok(playback)
+    }
+
+    fn stop(&self, playback: Playback) -> Result<()> {
+        if let Some(source) = self.loops.borrow_mut().remove(&playback) {
+            AsRef::<AudioScheduledSourceNode>::as_ref(&source)
+                .stop()
+                .map_err(|err| anyhow!("Could not stop buffer source {:#?}", err))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct NullAudioBackend {
+    next_sound: Cell<usize>,
+}
+
+impl NullAudioBackend {
+    fn new_sound(&self) -> Sound {
+        let sound = Sound(self.next_sound.get());
+        self.next_sound.set(sound.0 + 1);
+        sound
+    }
+}
+
+#[async_trait(?Send)]
+impl AudioBackend for NullAudioBackend {
+    async fn load(&self, _path: &str) -> Result<Sound> {
+        Ok(self.new_sound())
+    }
+
+    async fn decode(&self, _bytes: &[u8]) -> Result<Sound> {
+        Ok(self.new_sound())
+    }
+
+    fn upload(&self, _buffer: &PcmBuffer) -> Result<Sound> {
+        Ok(self.new_sound())
+    }
+
+    fn play(&self, _sound: Sound) -> Result<()> {
+        Ok(())
+    }
+
+    fn play_looping(&self, _sound: Sound) -> Result<Playback> {
+        Ok(Playback(0))
+    }
+
+    fn stop(&self, _playback: Playback) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AudioCall {
+    Load(String),
+    Decode(usize),
+    Upload(PcmBuffer),
+    Play(Sound),
+    PlayLooping(Sound, Playback),
+    Stop(Playback),
+}
+
+// Accepts everything without making a sound and keeps a log of what the game asked for.
+#[derive(Clone, Default)]
+pub struct RecordingAudioBackend {
+    calls: Rc<RefCell<Vec<AudioCall>>>,
+    next_sound: Rc<Cell<usize>>,
+    next_playback: Rc<Cell<usize>>,
+}
+
+impl RecordingAudioBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn calls(&self) -> Vec<AudioCall> {
+        self.calls.borrow().clone()
+    }
+
+    fn new_sound(&self, call: AudioCall) -> Sound {
+        self.calls.borrow_mut().push(call);
+        let sound = Sound(self.next_sound.get());
+        self.next_sound.set(sound.0 + 1);
+        sound
+    }
+}
+
+#[async_trait(?Send)]
+impl AudioBackend for RecordingAudioBackend {
+    async fn load(&self, path: &str) -> Result<Sound> {
+        Ok(self.new_sound(AudioCall::Load(path.to_string())))
+    }
+
+    async fn decode(&self, bytes: &[u8]) -> Result<Sound> {
+        Ok(self.new_sound(AudioCall::Decode(bytes.len())))
+    }
+
+    fn upload(&self, buffer: &PcmBuffer) -> Result<Sound> {
+        Ok(self.new_sound(AudioCall::Upload(buffer.clone())))
+    }
+
+    fn play(&self, sound: Sound) -> Result<()> {
+        self.calls.borrow_mut().push(AudioCall::Play(sound));
+        Ok(())
+    }
+
+    fn play_looping(&self, sound: Sound) -> Result<Playback> {
+        let playback = Playback(self.next_playback.get());
+        self.next_playback.set(playback.0 + 1);
+        self.calls
+            .borrow_mut()
+            .push(AudioCall::PlayLooping(sound, playback));
+        Ok(playback)
+    }
+
+    fn stop(&self, playback: Playback) -> Result<()> {
+        self.calls.borrow_mut().push(AudioCall::Stop(playback));
+        Ok(())
+    }
+}
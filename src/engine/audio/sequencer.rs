@@ -0,0 +1,72 @@
+use anyhow::Result;
+
+use super::{AudioBackend, Sound};
+
+// Plays a looping pattern of sounds, one per step. The tempo scales how fast
+// steps go by, so the pattern can follow gameplay speed.
+#[derive(Clone)]
+pub struct StepSequencer {
+    pattern: Vec<Sound>,
+    step_duration: f32,
+    acc_time: f32,
+    next_step: usize,
+    tempo: f32,
+    playing: bool,
+}
+
+impl StepSequencer {
+    pub fn new(pattern: Vec<Sound>, step_duration: f32) -> Self {
+        Self {
+            pattern,
+            step_duration,
+            // The first step sounds as soon as playback starts.
+            acc_time: step_duration,
+            next_step: 0,
+            tempo: 1.0,
+            playing: true,
+        }
+    }
+
+    pub fn set_tempo(&mut self, tempo: f32) {
+        self.tempo = tempo;
+    }
+
+    pub fn tempo(&self) -> f32 {
+        self.tempo
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn resume(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn next_step(&self) -> usize {
+        self.next_step
+    }
+
+    pub fn reset(&mut self) {
+        self.acc_time = self.step_duration;
+        self.next_step = 0;
+    }
+
+    pub fn update(&mut self, delta: f32, backend: &dyn AudioBackend) -> Result<()> {
+        if !self.playing || self.pattern.is_empty() || self.step_duration <= 0.0 {
+            return Ok(());
+        }
+
+        self.acc_time += delta * self.tempo;
+        while self.step_duration <= self.acc_time {
+            self.acc_time -= self.step_duration;
+            backend.play(self.pattern[self.next_step])?;
+            self.next_step = (self.next_step + 1) % self.pattern.len();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use std::f32::consts::PI;
+
+use crate::engine::random::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Waveform {
+    Square,
+    Sawtooth,
+    Noise,
+}
+
+// Stage lengths in seconds: linear rise, full volume, linear fall.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Envelope {
+    pub attack: f32,
+    pub sustain: f32,
+    pub decay: f32,
+}
+
+impl Envelope {
+    pub fn duration(&self) -> f32 {
+        self.attack + self.sustain + self.decay
+    }
+
+    fn amplitude(&self, t: f32) -> f32 {
+        if t < self.attack {
+            t / self.attack
+        } else if t < self.attack + self.sustain {
+            1.0
+        } else if t < self.duration() {
+            1.0 - (t - self.attack - self.sustain) / self.decay
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SfxParams {
+    pub waveform: Waveform,
+    pub envelope: Envelope,
+    pub frequency: f32,
+    // Hz per second, clamped at `min_frequency` when sliding down.
+    pub frequency_slide: f32,
+    pub min_frequency: f32,
+    // Depth is relative to the current frequency.
+    pub vibrato_depth: f32,
+    pub vibrato_speed: f32,
+    pub duty: f32,
+    pub volume: f32,
+}
+
+impl Default for SfxParams {
+    fn default() -> Self {
+        Self {
+            waveform: Waveform::Square,
+            envelope: Envelope {
+                attack: 0.0,
+                sustain: 0.1,
+                decay: 0.1,
+            },
+            frequency: 440.0,
+            frequency_slide: 0.0,
+            min_frequency: 20.0,
+            vibrato_depth: 0.0,
+            vibrato_speed: 0.0,
+            duty: 0.5,
+            volume: 0.5,
+        }
+    }
+}
+
+impl SfxParams {
+    const NOISE_SEED: u64 = 0x5f3759df;
+
+    pub fn synthesize(&self, sample_rate: u32) -> PcmBuffer {
+        let length = (self.envelope.duration() * sample_rate as f32).ceil() as usize;
+        let mut rng = Rng::new(Self::NOISE_SEED);
+        let mut noise = rng.next_f32() * 2.0 - 1.0;
+        let mut phase = 0.0;
+
+        let samples = (0..length)
+            .map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                let vibrato = 1.0 + self.vibrato_depth * (2.0 * PI * self.vibrato_speed * t).sin();
+                let frequency =
+                    (self.frequency + self.frequency_slide * t).max(self.min_frequency) * vibrato;
+
+                phase += frequency / sample_rate as f32;
+                if 1.0 <= phase {
+                    phase %= 1.0;
+                    noise = rng.next_f32() * 2.0 - 1.0;
+                }
+
+                let oscillator = match self.waveform {
+                    Waveform::Square if phase < self.duty => 1.0,
+                    Waveform::Square => -1.0,
+                    Waveform::Sawtooth => 2.0 * phase - 1.0,
+                    Waveform::Noise => noise,
+                };
+                oscillator * self.envelope.amplitude(t) * self.volume
+            })
+            .collect();
+
+        PcmBuffer::new(sample_rate, samples)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PcmBuffer {
+    sample_rate: u32,
+    samples: Vec<f32>,
+}
+
+impl PcmBuffer {
+    pub fn new(sample_rate: u32, samples: Vec<f32>) -> Self {
+        Self {
+            sample_rate,
+            samples,
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.samples.len() as f32 / self.sample_rate as f32
+    }
+
+    pub fn peak(&self) -> f32 {
+        self.samples
+            .iter()
+            .fold(0.0, |peak, sample| peak.max(sample.abs()))
+    }
+}
@@ -4,14 +4,18 @@ use js_sys::ArrayBuffer;
 use std::future::Future;
 use web_sys::HtmlImageElement;
 
-use wasm_bindgen::closure::{WasmClosure};
+use wasm_bindgen::closure::WasmClosure;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::Clamped;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{console, CanvasRenderingContext2d, Document, HtmlCanvasElement, Response, Window};
+use web_sys::{
+    console, Blob, CanvasRenderingContext2d, Document, Gamepad, HtmlAnchorElement,
+    HtmlCanvasElement, ImageData, Response, Storage, Url, UrlSearchParams, Window,
+};
 
 pub type LoopClosure = Closure<dyn FnMut(f64)>;
 
-fn window() -> Result<Window> {
+pub fn window() -> Result<Window> {
     web_sys::window().ok_or_else(|| anyhow!("No Window Found"))
 }
 
@@ -21,6 +25,35 @@ pub fn document() -> Result<Document> {
         .ok_or_else(|| anyhow!("No Ducment Found"))
 }
 
+pub fn query_param(name: &str) -> Result<Option<String>> {
+    let search = window()?
+        .location()
+        .search()
+        .map_err(|err| anyhow!("Could not read location {:#?}", err))?;
+    let params = UrlSearchParams::new_with_str(&search)
+        .map_err(|err| anyhow!("Could not parse query {:#?}", err))?;
+    Ok(params.get(name))
+}
+
+// Saves `contents` through a temporary link, as if the user had clicked a
+// download.
+pub fn download(file_name: &str, contents: &str) -> Result<()> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let blob = Blob::new_with_str_sequence(&parts)
+        .map_err(|err| anyhow!("Could not create blob {:#?}", err))?;
+    let url = Url::create_object_url_with_blob(&blob)
+        .map_err(|err| anyhow!("Could not create object url {:#?}", err))?;
+    let link: HtmlAnchorElement = document()?
+        .create_element("a")
+        .map_err(|err| anyhow!("Could not create link {:#?}", err))?
+        .dyn_into()
+        .map_err(|element| anyhow!("Error converting {:#?} to HtmlAnchorElement", element))?;
+    link.set_href(&url);
+    link.set_download(file_name);
+    link.click();
+    Url::revoke_object_url(&url).map_err(|err| anyhow!("Could not revoke object url {:#?}", err))
+}
+
 pub fn canvas() -> Result<HtmlCanvasElement> {
     document()?
         .get_element_by_id("canvas")
@@ -30,7 +63,19 @@ pub fn canvas() -> Result<HtmlCanvasElement> {
 }
 
 pub fn context() -> Result<CanvasRenderingContext2d> {
-    canvas()?
+    context_2d(&canvas()?)
+}
+
+pub fn new_canvas() -> Result<HtmlCanvasElement> {
+    document()?
+        .create_element("canvas")
+        .map_err(|err| anyhow!("Could not create canvas {:#?}", err))?
+        .dyn_into::<web_sys::HtmlCanvasElement>()
+        .map_err(|element| anyhow!("Error converting {:#?} to HtmlCanvasElement", element))
+}
+
+pub fn context_2d(canvas: &HtmlCanvasElement) -> Result<CanvasRenderingContext2d> {
+    canvas
         .get_context("2d")
         .map_err(|js_value| anyhow!("Error getting 2d context {:#?}", js_value))?
         .ok_or_else(|| anyhow!("No 2d context found"))?
@@ -38,6 +83,25 @@ pub fn context() -> Result<CanvasRenderingContext2d> {
         .map_err(|element| anyhow!("Error converting {:#?} to CanvasRenderContext2d", element))
 }
 
+pub fn local_storage() -> Result<Storage> {
+    window()?
+        .local_storage()
+        .map_err(|err| anyhow!("Could not access localStorage {:#?}", err))?
+        .ok_or_else(|| anyhow!("No localStorage found"))
+}
+
+// Disconnected slots come back as null and are skipped.
+pub fn gamepads() -> Result<Vec<Gamepad>> {
+    Ok(window()?
+        .navigator()
+        .get_gamepads()
+        .map_err(|err| anyhow!("Could not get gamepads {:#?}", err))?
+        .iter()
+        .filter_map(|pad| pad.dyn_into::<Gamepad>().ok())
+        .filter(|pad| pad.connected())
+        .collect())
+}
+
 pub fn spawn_local<F>(future: F)
 where
     F: Future<Output = ()> + 'static,
@@ -69,6 +133,19 @@ pub async fn fetch_json(json_path: &str) -> Result<JsValue> {
     .map_err(|err| anyhow!("error fetching JSON {:#?}", err))
 }
 
+pub async fn fetch_text(path: &str) -> Result<String> {
+    let text = fetch_response(path)
+        .await?
+        .text()
+        .map_err(|err| anyhow!("Could not get text from response {:#?}", err))?;
+
+    JsFuture::from(text)
+        .await
+        .map_err(|err| anyhow!("error fetching text {:#?}", err))?
+        .as_string()
+        .ok_or_else(|| anyhow!("Response text is not a string"))
+}
+
 pub async fn fetch_array_buffer(json_path: &str) -> Result<ArrayBuffer> {
     let array_buffer = fetch_response(json_path)
         .await?
@@ -86,13 +163,17 @@ pub fn new_image() -> Result<HtmlImageElement> {
     HtmlImageElement::new().map_err(|err| anyhow!("Could not create HtmlImageElement: {:#?}", err))
 }
 
+pub fn new_image_data(pixels: &[u8], width: u32, height: u32) -> Result<ImageData> {
+    ImageData::new_with_u8_clamped_array_and_sh(Clamped(pixels), width, height)
+        .map_err(|err| anyhow!("Could not create ImageData: {:#?}", err))
+}
+
 pub fn request_animation_frame(callback: &Closure<dyn FnMut(f64)>) -> Result<i32> {
     window()?
         .request_animation_frame(callback.as_ref().unchecked_ref())
         .map_err(|err| anyhow!("Cannot request animation frame {:#?}", err))
 }
 
-
 pub fn closure_wrap<T: WasmClosure + ?Sized>(data: Box<T>) -> Closure<T> {
     Closure::wrap(data)
 }
@@ -0,0 +1,45 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use super::browser;
+
+pub trait Clock {
+    fn now(&self) -> Result<f64>;
+}
+
+pub struct BrowserClock;
+
+impl Clock for BrowserClock {
+    fn now(&self) -> Result<f64> {
+        browser::now()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    now: Rc<Cell<f64>>,
+}
+
+impl ManualClock {
+    pub fn new(now: f64) -> Self {
+        Self {
+            now: Rc::new(Cell::new(now)),
+        }
+    }
+
+    pub fn set(&self, now: f64) {
+        self.now.set(now);
+    }
+
+    pub fn advance(&self, delta: f64) {
+        self.now.set(self.now.get() + delta);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Result<f64> {
+        Ok(self.now.get())
+    }
+}
@@ -1,11 +1,14 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use futures::channel::mpsc::{unbounded, UnboundedReceiver};
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
 use super::browser;
+use super::gamepad::GamepadButton;
+use super::geometry::Point;
 
 enum KeyPress {
     KeyUp(web_sys::KeyboardEvent),
@@ -43,9 +46,230 @@ fn prepare_key_input() -> Result<UnboundedReceiver<KeyPress>> {
     Ok(keyevent_receiver)
 }
 
+fn prepare_pointer_input() -> Result<UnboundedReceiver<Event>> {
+    let (sender, receiver) = unbounded();
+    let sender = Rc::new(RefCell::new(sender));
+    let canvas = browser::canvas()?;
+
+    let listen = |to_event: fn(Pointer) -> Event| {
+        let sender = sender.clone();
+        let canvas = canvas.clone();
+        browser::closure_wrap(Box::new(move |evt: web_sys::PointerEvent| {
+            let bounds = CanvasBounds::of(&canvas);
+            let pointer = Pointer {
+                id: evt.pointer_id(),
+                kind: PointerKind::from(evt.pointer_type().as_str()),
+                position: bounds.to_canvas_space(evt.client_x() as f64, evt.client_y() as f64),
+                button: PointerButton::from_index(evt.button()),
+            };
+            sender
+                .borrow_mut()
+                .start_send(to_event(pointer))
+                .expect("Failed to start Pointer send");
+        }) as Box<dyn FnMut(web_sys::PointerEvent)>)
+    };
+
+    let onpointerdown = listen(Event::PointerDown);
+    let onpointermove = listen(Event::PointerMove);
+    let onpointerup = listen(Event::PointerUp);
+
+    // Keep receiving moves and the release after a press leaves the canvas.
+    let capture = canvas.clone();
+    let oncapture = browser::closure_wrap(Box::new(move |evt: web_sys::PointerEvent| {
+        capture.set_pointer_capture(evt.pointer_id()).ok();
+    }) as Box<dyn FnMut(web_sys::PointerEvent)>);
+
+    canvas.set_onpointerdown(Some(onpointerdown.as_ref().unchecked_ref()));
+    canvas.set_onpointermove(Some(onpointermove.as_ref().unchecked_ref()));
+    canvas.set_onpointerup(Some(onpointerup.as_ref().unchecked_ref()));
+    canvas.set_onpointercancel(Some(onpointerup.as_ref().unchecked_ref()));
+    canvas
+        .add_event_listener_with_callback("pointerdown", oncapture.as_ref().unchecked_ref())
+        .map_err(|err| anyhow!("Could not listen for pointerdown {:#?}", err))?;
+    onpointerdown.forget();
+    onpointermove.forget();
+    onpointerup.forget();
+    oncapture.forget();
+
+    Ok(receiver)
+}
+
+fn prepare_window_input() -> Result<UnboundedReceiver<Event>> {
+    let (sender, receiver) = unbounded();
+    let sender = Rc::new(RefCell::new(sender));
+    let canvas = browser::canvas()?;
+    let document = browser::document()?;
+
+    let send = move |event: Event| {
+        sender
+            .borrow_mut()
+            .start_send(event)
+            .expect("Failed to start window event send");
+    };
+
+    let on_blur = send.clone();
+    let onblur =
+        browser::closure_wrap(Box::new(move || on_blur(Event::FocusLost)) as Box<dyn FnMut()>);
+    let on_focus = send.clone();
+    let onfocus =
+        browser::closure_wrap(Box::new(move || on_focus(Event::FocusGained)) as Box<dyn FnMut()>);
+    let on_visibility = send.clone();
+    let visibility_document = document.clone();
+    let onvisibilitychange = browser::closure_wrap(Box::new(move || {
+        on_visibility(Event::VisibilityChanged(!visibility_document.hidden()))
+    }) as Box<dyn FnMut()>);
+    let resized_canvas = canvas.clone();
+    let onresize = browser::closure_wrap(Box::new(move || {
+        let rect = resized_canvas.get_bounding_client_rect();
+        send(Event::Resized {
+            width: rect.width().round() as u32,
+            height: rect.height().round() as u32,
+        })
+    }) as Box<dyn FnMut()>);
+
+    canvas.set_onblur(Some(onblur.as_ref().unchecked_ref()));
+    canvas.set_onfocus(Some(onfocus.as_ref().unchecked_ref()));
+    document.set_onvisibilitychange(Some(onvisibilitychange.as_ref().unchecked_ref()));
+    browser::window()?.set_onresize(Some(onresize.as_ref().unchecked_ref()));
+    onblur.forget();
+    onfocus.forget();
+    onvisibilitychange.forget();
+    onresize.forget();
+
+    Ok(receiver)
+}
+
+// Where the canvas sits on the page. CSS may display it at a different size
+// than its backing store, so client coordinates have to be rescaled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CanvasBounds {
+    pub left: f64,
+    pub top: f64,
+    pub width: f64,
+    pub height: f64,
+    pub canvas_width: u32,
+    pub canvas_height: u32,
+}
+
+impl CanvasBounds {
+    fn of(canvas: &web_sys::HtmlCanvasElement) -> Self {
+        let rect = canvas.get_bounding_client_rect();
+        Self {
+            left: rect.left(),
+            top: rect.top(),
+            width: rect.width(),
+            height: rect.height(),
+            canvas_width: canvas.width(),
+            canvas_height: canvas.height(),
+        }
+    }
+
+    pub fn to_canvas_space(&self, client_x: f64, client_y: f64) -> Point {
+        let scale = |offset: f64, displayed: f64, backing: u32| {
+            if displayed <= 0.0 {
+                return offset;
+            }
+            offset * backing as f64 / displayed
+        };
+        Point {
+            x: scale(client_x - self.left, self.width, self.canvas_width).floor() as i16,
+            y: scale(client_y - self.top, self.height, self.canvas_height).floor() as i16,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PointerKind {
+    Mouse,
+    Touch,
+    Pen,
+}
+
+impl From<&str> for PointerKind {
+    fn from(pointer_type: &str) -> Self {
+        match pointer_type {
+            "touch" => PointerKind::Touch,
+            "pen" => PointerKind::Pen,
+            _ => PointerKind::Mouse,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PointerButton {
+    Primary,
+    Middle,
+    Secondary,
+}
+
+impl PointerButton {
+    // `MouseEvent.button` numbering; moves report -1 for "no button".
+    pub fn from_index(index: i16) -> Option<Self> {
+        match index {
+            0 => Some(PointerButton::Primary),
+            1 => Some(PointerButton::Middle),
+            2 => Some(PointerButton::Secondary),
+            _ => None,
+        }
+    }
+}
+
+// `id` tells apart simultaneous touches; it stays the same from down to up.
+// `button` is the button that changed, so it is only set on downs and ups.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pointer {
+    pub id: i32,
+    pub kind: PointerKind,
+    pub position: Point,
+    pub button: Option<PointerButton>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Event {
     KeyUp(String),
     KeyDown(String),
+    GamepadConnected(u32),
+    GamepadDisconnected(u32),
+    GamepadButtonDown(u32, GamepadButton),
+    GamepadButtonUp(u32, GamepadButton),
+    PointerDown(Pointer),
+    PointerMove(Pointer),
+    PointerUp(Pointer),
+    // The canvas stopped or started receiving keys, e.g. the player clicked
+    // elsewhere or switched windows.
+    FocusLost,
+    FocusGained,
+    VisibilityChanged(bool),
+    // The canvas' displayed size in CSS pixels.
+    Resized { width: u32, height: u32 },
+}
+
+impl Event {
+    // Keys released while the page is in the background never arrive, so
+    // anything tracking held input should let go on these.
+    pub fn is_focus_loss(&self) -> bool {
+        matches!(self, Event::FocusLost | Event::VisibilityChanged(false))
+    }
+
+    // Keys and gamepad buttons share one code space, so either can be bound
+    // to an action. Returns the code and whether it went down.
+    pub fn button(&self) -> Option<(String, bool)> {
+        match self {
+            Event::KeyDown(code) => Some((code.clone(), true)),
+            Event::KeyUp(code) => Some((code.clone(), false)),
+            Event::GamepadButtonDown(_, button) => Some((button.code(), true)),
+            Event::GamepadButtonUp(_, button) => Some((button.code(), false)),
+            Event::GamepadConnected(_)
+            | Event::GamepadDisconnected(_)
+            | Event::PointerDown(_)
+            | Event::PointerMove(_)
+            | Event::PointerUp(_)
+            | Event::FocusLost
+            | Event::FocusGained
+            | Event::VisibilityChanged(_)
+            | Event::Resized { .. } => None,
+        }
+    }
 }
 
 impl From<KeyPress> for Event {
@@ -59,24 +283,75 @@ impl From<KeyPress> for Event {
 
 pub trait EventSource {
     fn try_next(&mut self) -> Option<Event>;
+
+    fn begin_update(&mut self, _frame: u64, _timestamp: f64) {}
+
+    // Sees the delta of the update just fed and may replace it, so a replay
+    // can reproduce the recorded frame times.
+    fn update_delta(&mut self, delta: f64) -> f64 {
+        delta
+    }
+
+    fn chain<B: EventSource>(self, other: B) -> Chain<Self, B>
+    where
+        Self: Sized,
+    {
+        Chain {
+            first: self,
+            second: other,
+        }
+    }
+}
+
+// Drains `first` before `second` each frame.
+pub struct Chain<A: EventSource, B: EventSource> {
+    first: A,
+    second: B,
+}
+
+impl<A: EventSource, B: EventSource> EventSource for Chain<A, B> {
+    fn try_next(&mut self) -> Option<Event> {
+        self.first.try_next().or_else(|| self.second.try_next())
+    }
+
+    fn begin_update(&mut self, frame: u64, timestamp: f64) {
+        self.first.begin_update(frame, timestamp);
+        self.second.begin_update(frame, timestamp);
+    }
+
+    fn update_delta(&mut self, delta: f64) -> f64 {
+        let delta = self.first.update_delta(delta);
+        self.second.update_delta(delta)
+    }
 }
 
 pub struct BrowserEventSource {
     keyevent_receiver: UnboundedReceiver<KeyPress>,
+    pointer_receiver: UnboundedReceiver<Event>,
+    window_receiver: UnboundedReceiver<Event>,
 }
 
 impl BrowserEventSource {
     pub fn new() -> Result<Self> {
         let keyevent_receiver = prepare_key_input()?;
-        Ok(Self { keyevent_receiver })
+        let pointer_receiver = prepare_pointer_input()?;
+        let window_receiver = prepare_window_input()?;
+        Ok(Self {
+            keyevent_receiver,
+            pointer_receiver,
+            window_receiver,
+        })
     }
 }
 
 impl EventSource for BrowserEventSource {
     fn try_next(&mut self) -> Option<Event> {
-        match self.keyevent_receiver.try_next() {
-            Ok(Some(evt)) => Some(evt.into()),
-            _ => None,
+        if let Ok(evt) = self.keyevent_receiver.try_recv() {
+            return Some(evt.into());
+        }
+        if let Ok(evt) = self.pointer_receiver.try_recv() {
+            return Some(evt);
         }
+        self.window_receiver.try_recv().ok()
     }
 }
@@ -0,0 +1,89 @@
+use std::rc::Rc;
+
+use super::geometry::Point;
+use super::sprite::{Cell, SpriteSheet};
+use super::DrawCommand;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Clone)]
+pub struct BitmapFont {
+    sprite_sheet: Rc<SpriteSheet>,
+    spacing: i16,
+    scale: i16,
+}
+
+impl BitmapFont {
+    const DEFAULT_SPACING: i16 = 2;
+    const BLANK_WIDTH: i16 = 8;
+
+    pub fn new(sprite_sheet: Rc<SpriteSheet>) -> Self {
+        Self {
+            sprite_sheet,
+            spacing: Self::DEFAULT_SPACING,
+            scale: 1,
+        }
+    }
+
+    pub fn with_spacing(mut self, spacing: i16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: i16) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn glyph(&self, c: char) -> Option<&Cell> {
+        self.sprite_sheet
+            .cell(&format!("{}.png", c.to_ascii_uppercase()))
+    }
+
+    pub fn measure(&self, text: &str) -> i16 {
+        let advances: i16 = text.chars().map(|c| self.advance(c)).sum();
+        let gaps = text.chars().count().saturating_sub(1) as i16;
+        advances + gaps * self.spacing * self.scale
+    }
+
+    pub fn draw(&self, text: &str, position: &Point, align: Align, layer: u8) -> DrawCommand {
+        let width = self.measure(text);
+        let mut x = match align {
+            Align::Left => position.x,
+            Align::Center => position.x - width / 2,
+            Align::Right => position.x - width,
+        };
+
+        let mut glyphs = vec![];
+        for c in text.chars() {
+            if let Some(cell) = self.glyph(c) {
+                glyphs.push((cell.clone(), Point { x, y: position.y }));
+            }
+            x += self.advance(c) + self.spacing * self.scale;
+        }
+
+        let sprite_sheet = self.sprite_sheet.clone();
+        let scale = self.scale;
+        DrawCommand(
+            layer,
+            Box::new(move |renderer| {
+                for (cell, position) in glyphs.iter() {
+                    sprite_sheet.draw_scaled(renderer, cell, position, scale);
+                }
+            }),
+        )
+    }
+
+    fn advance(&self, c: char) -> i16 {
+        let width = self
+            .glyph(c)
+            .map(|cell| cell.shape().width)
+            .unwrap_or(Self::BLANK_WIDTH);
+        width * self.scale
+    }
+}
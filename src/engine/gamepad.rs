@@ -0,0 +1,216 @@
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::rc::Rc;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+
+use super::browser;
+use super::event::{Event, EventSource};
+
+// Buttons follow the W3C "standard" gamepad layout, named by position so
+// that they read the same on any brand of controller. The left stick is
+// reported as four extra directional buttons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GamepadButton {
+    South,
+    East,
+    West,
+    North,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    StickUp,
+    StickDown,
+    StickLeft,
+    StickRight,
+}
+
+impl GamepadButton {
+    const STANDARD_BUTTONS: [(usize, GamepadButton); 10] = [
+        (0, GamepadButton::South),
+        (1, GamepadButton::East),
+        (2, GamepadButton::West),
+        (3, GamepadButton::North),
+        (8, GamepadButton::Select),
+        (9, GamepadButton::Start),
+        (12, GamepadButton::DPadUp),
+        (13, GamepadButton::DPadDown),
+        (14, GamepadButton::DPadLeft),
+        (15, GamepadButton::DPadRight),
+    ];
+
+    // The code the button is bound by, alongside keyboard codes.
+    pub fn code(&self) -> String {
+        format!("Pad{:?}", self)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GamepadSnapshot {
+    pub index: u32,
+    pub buttons: Vec<bool>,
+    pub axes: Vec<f64>,
+}
+
+impl GamepadSnapshot {
+    fn pressed_buttons(&self, dead_zone: f64) -> BTreeSet<GamepadButton> {
+        let mut pressed: BTreeSet<GamepadButton> = GamepadButton::STANDARD_BUTTONS
+            .iter()
+            .filter(|(i, _)| self.buttons.get(*i).copied().unwrap_or(false))
+            .map(|(_, button)| *button)
+            .collect();
+
+        let x = self.axes.first().copied().unwrap_or(0.0);
+        let y = self.axes.get(1).copied().unwrap_or(0.0);
+        if x < -dead_zone {
+            pressed.insert(GamepadButton::StickLeft);
+        }
+        if dead_zone < x {
+            pressed.insert(GamepadButton::StickRight);
+        }
+        if y < -dead_zone {
+            pressed.insert(GamepadButton::StickUp);
+        }
+        if dead_zone < y {
+            pressed.insert(GamepadButton::StickDown);
+        }
+        pressed
+    }
+}
+
+pub trait GamepadPoller {
+    fn poll(&mut self) -> Result<Vec<GamepadSnapshot>>;
+}
+
+pub struct BrowserGamepads;
+
+impl GamepadPoller for BrowserGamepads {
+    fn poll(&mut self) -> Result<Vec<GamepadSnapshot>> {
+        Ok(browser::gamepads()?
+            .iter()
+            .map(|pad| GamepadSnapshot {
+                index: pad.index(),
+                buttons: pad
+                    .buttons()
+                    .iter()
+                    .map(|button| {
+                        button
+                            .dyn_into::<web_sys::GamepadButton>()
+                            .map(|button| button.pressed())
+                            .unwrap_or(false)
+                    })
+                    .collect(),
+                axes: pad
+                    .axes()
+                    .iter()
+                    .map(|axis| axis.as_f64().unwrap_or(0.0))
+                    .collect(),
+            })
+            .collect())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ManualGamepads {
+    pads: Rc<RefCell<Vec<GamepadSnapshot>>>,
+}
+
+impl ManualGamepads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, pads: Vec<GamepadSnapshot>) {
+        *self.pads.borrow_mut() = pads;
+    }
+}
+
+impl GamepadPoller for ManualGamepads {
+    fn poll(&mut self) -> Result<Vec<GamepadSnapshot>> {
+        Ok(self.pads.borrow().clone())
+    }
+}
+
+// Polls once per frame and reports what changed since the previous poll.
+pub struct GamepadEventSource<P: GamepadPoller> {
+    poller: P,
+    dead_zone: f64,
+    pressed: HashMap<u32, BTreeSet<GamepadButton>>,
+    pending: VecDeque<Event>,
+}
+
+impl<P: GamepadPoller> GamepadEventSource<P> {
+    const DEFAULT_DEAD_ZONE: f64 = 0.4;
+
+    pub fn new(poller: P) -> Self {
+        Self {
+            poller,
+            dead_zone: Self::DEFAULT_DEAD_ZONE,
+            pressed: HashMap::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub fn with_dead_zone(mut self, dead_zone: f64) -> Self {
+        self.dead_zone = dead_zone;
+        self
+    }
+
+    fn apply(&mut self, pads: Vec<GamepadSnapshot>) {
+        let connected: Vec<u32> = pads.iter().map(|pad| pad.index).collect();
+        let mut disconnected: Vec<u32> = self
+            .pressed
+            .keys()
+            .filter(|index| !connected.contains(index))
+            .copied()
+            .collect();
+        disconnected.sort();
+        for index in disconnected {
+            let released = self.pressed.remove(&index).unwrap_or_default();
+            for button in released {
+                self.pending
+                    .push_back(Event::GamepadButtonUp(index, button));
+            }
+            self.pending.push_back(Event::GamepadDisconnected(index));
+        }
+
+        for pad in pads {
+            let now = pad.pressed_buttons(self.dead_zone);
+            let before = match self.pressed.get(&pad.index) {
+                Some(before) => before.clone(),
+                None => {
+                    self.pending.push_back(Event::GamepadConnected(pad.index));
+                    BTreeSet::new()
+                }
+            };
+
+            for button in before.difference(&now) {
+                self.pending
+                    .push_back(Event::GamepadButtonUp(pad.index, *button));
+            }
+            for button in now.difference(&before) {
+                self.pending
+                    .push_back(Event::GamepadButtonDown(pad.index, *button));
+            }
+            self.pressed.insert(pad.index, now);
+        }
+    }
+}
+
+impl<P: GamepadPoller> EventSource for GamepadEventSource<P> {
+    fn try_next(&mut self) -> Option<Event> {
+        self.pending.pop_front()
+    }
+
+    fn begin_update(&mut self, _frame: u64, _timestamp: f64) {
+        match self.poller.poll() {
+            Ok(pads) => self.apply(pads),
+            Err(err) => browser::log(&format!("Could not poll gamepads: {:#?}", err)),
+        }
+    }
+}
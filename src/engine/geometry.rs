@@ -1,9 +1,21 @@
-#[derive(Clone, Copy, Default, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Point {
     pub x: i16,
     pub y: i16,
 }
 
+impl Point {
+    pub fn lerp(&self, to: &Point, alpha: f32) -> Point {
+        let lerp = |from: i16, to: i16| from + ((to - from) as f32 * alpha).round() as i16;
+        Point {
+            x: lerp(self.x, to.x),
+            y: lerp(self.y, to.y),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Default)]
 pub struct Shape {
     pub width: i16,
@@ -32,6 +44,21 @@ impl Rect {
             && (rect.y() < (self.bottom()))
     }
 
+    pub fn contains(&self, point: &Point) -> bool {
+        self.left() <= point.x
+            && point.x < self.right()
+            && self.top() <= point.y
+            && point.y < self.bottom()
+    }
+
+    // The point inside the rect that is closest to `point`.
+    pub fn clamp(&self, point: &Point) -> Point {
+        Point {
+            x: point.x.clamp(self.left(), self.right() - 1),
+            y: point.y.clamp(self.top(), self.bottom() - 1),
+        }
+    }
+
     pub fn x(&self) -> i16 {
         self.origin.x
     }
@@ -64,6 +91,13 @@ impl Rect {
         self.y() + self.height()
     }
 
+    pub fn center(&self) -> Point {
+        Point {
+            x: self.x() + self.width() / 2,
+            y: self.y() + self.height() / 2,
+        }
+    }
+
     pub fn set_x(&mut self, x: i16) {
         self.origin.x = x
     }
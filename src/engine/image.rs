@@ -0,0 +1,165 @@
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+use web_sys::HtmlImageElement;
+
+#[derive(Clone)]
+pub enum Image {
+    Element(HtmlImageElement),
+    Bitmap(Rc<Bitmap>),
+}
+
+impl Image {
+    pub fn element(&self) -> Option<&HtmlImageElement> {
+        match self {
+            Self::Element(element) => Some(element),
+            Self::Bitmap(_) => None,
+        }
+    }
+
+    pub fn bitmap(&self) -> Option<&Bitmap> {
+        match self {
+            Self::Element(_) => None,
+            Self::Bitmap(bitmap) => Some(bitmap),
+        }
+    }
+}
+
+impl From<HtmlImageElement> for Image {
+    fn from(element: HtmlImageElement) -> Self {
+        Self::Element(element)
+    }
+}
+
+impl From<Bitmap> for Image {
+    fn from(bitmap: Bitmap) -> Self {
+        Self::Bitmap(Rc::new(bitmap))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Bitmap {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Bitmap {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self> {
+        if pixels.len() != (width * height * 4) as usize {
+            return Err(anyhow!(
+                "Expected {} bytes for a {}x{} RGBA bitmap, got {}",
+                width * height * 4,
+                width,
+                height,
+                pixels.len()
+            ));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn from_png(bytes: &[u8]) -> Result<Self> {
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::EXPAND);
+        let mut reader = decoder
+            .read_info()
+            .map_err(|err| anyhow!("Could not read png header {:#?}", err))?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buffer)
+            .map_err(|err| anyhow!("Could not decode png {:#?}", err))?;
+        buffer.truncate(info.buffer_size());
+
+        let pixels = match (info.color_type, info.bit_depth) {
+            (png::ColorType::Rgba, png::BitDepth::Eight) => buffer,
+            (png::ColorType::Rgb, png::BitDepth::Eight) => buffer
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 0xff])
+                .collect(),
+            (color_type, bit_depth) => {
+                return Err(anyhow!(
+                    "Unsupported png format {:?} {:?}",
+                    color_type,
+                    bit_depth
+                ))
+            }
+        };
+
+        Self::from_rgba(info.width, info.height, pixels)
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        {
+            let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder
+                .write_header()
+                .map_err(|err| anyhow!("Could not write png header {:#?}", err))?;
+            writer
+                .write_image_data(&self.pixels)
+                .map_err(|err| anyhow!("Could not encode png {:#?}", err))?;
+        }
+        Ok(bytes)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        let offset = self.offset(x as i32, y as i32)?;
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.pixels[offset..offset + 4]);
+        Some(pixel)
+    }
+
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: [u8; 4]) {
+        if let Some(offset) = self.offset(x, y) {
+            self.pixels[offset..offset + 4].copy_from_slice(&color);
+        }
+    }
+
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: [u8; 4]) {
+        let Some(offset) = self.offset(x, y) else {
+            return;
+        };
+
+        let alpha = color[3] as u32;
+        let destination = &mut self.pixels[offset..offset + 4];
+        for i in 0..3 {
+            let blended = (color[i] as u32 * alpha + destination[i] as u32 * (255 - alpha)) / 255;
+            destination[i] = blended as u8;
+        }
+        destination[3] = (alpha + destination[3] as u32 * (255 - alpha) / 255) as u8;
+    }
+
+    fn offset(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || self.width as i32 <= x || self.height as i32 <= y {
+            return None;
+        }
+        Some(((y as u32 * self.width + x as u32) * 4) as usize)
+    }
+}
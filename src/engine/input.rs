@@ -0,0 +1,217 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::event::Event;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionEvent<A> {
+    Pressed(A),
+    Released(A),
+}
+
+// Maps key codes (`KeyboardEvent.code`, so bindings follow physical key
+// positions rather than layout labels) and gamepad button codes onto game
+// actions. A key belongs to at most one action, while an action may have any
+// number of keys.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KeyBindings<A: Ord> {
+    bindings: BTreeMap<A, Vec<String>>,
+}
+
+impl<A: Copy + Ord> KeyBindings<A> {
+    pub fn new() -> Self {
+        Self {
+            bindings: BTreeMap::new(),
+        }
+    }
+
+    pub fn with(mut self, action: A, codes: &[&str]) -> Self {
+        for code in codes {
+            self.bind(action, code);
+        }
+        self
+    }
+
+    pub fn bind(&mut self, action: A, code: &str) {
+        self.unbind(code);
+        self.bindings
+            .entry(action)
+            .or_default()
+            .push(code.to_string());
+    }
+
+    pub fn unbind(&mut self, code: &str) {
+        for codes in self.bindings.values_mut() {
+            codes.retain(|bound| bound != code);
+        }
+    }
+
+    pub fn clear(&mut self, action: A) {
+        self.bindings.remove(&action);
+    }
+
+    pub fn codes(&self, action: A) -> &[String] {
+        self.bindings
+            .get(&action)
+            .map(|codes| codes.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn action(&self, code: &str) -> Option<A> {
+        self.bindings
+            .iter()
+            .find(|(_, codes)| codes.iter().any(|bound| bound == code))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn translate(&self, event: &Event) -> Option<ActionEvent<A>> {
+        let (code, down) = event.button()?;
+        let action = self.action(&code)?;
+        if down {
+            Some(ActionEvent::Pressed(action))
+        } else {
+            Some(ActionEvent::Released(action))
+        }
+    }
+}
+
+impl<A: Copy + Ord + Serialize> KeyBindings<A> {
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self)
+            .map_err(|err| anyhow!("Could not serialize key bindings {:#?}", err))
+    }
+}
+
+impl<A: Copy + Ord + for<'de> Deserialize<'de>> KeyBindings<A> {
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|err| anyhow!("Could not deserialize key bindings {:#?}", err))
+    }
+}
+
+impl<A: Copy + Ord> Default for KeyBindings<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Which keys are held this frame, and which went down or up since the last
+// one. Browser auto-repeat resends `KeyDown` for a held key; those are ignored
+// here, so a key is "just pressed" once per physical press.
+#[derive(Clone, Debug, Default)]
+pub struct InputState {
+    held: HashMap<String, Held>,
+    presses: u64,
+    previous: HashSet<String>,
+    pressed: HashSet<String>,
+    released: HashSet<String>,
+}
+
+// Every pad shares the same button codes, so a code stays held until each
+// source pressing it has let go. The keyboard is the `None` source.
+#[derive(Clone, Debug)]
+struct Held {
+    // The number of the press that put the code down.
+    press: u64,
+    sources: HashSet<Option<u32>>,
+}
+
+impl InputState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, events: &[Event]) {
+        self.previous = self.held.keys().cloned().collect();
+        self.pressed.clear();
+        self.released.clear();
+        events.iter().for_each(|event| self.apply(event));
+    }
+
+    fn apply(&mut self, event: &Event) {
+        if event.is_focus_loss() {
+            self.released
+                .extend(self.held.drain().map(|(code, _)| code));
+            return;
+        }
+
+        let source = match event {
+            Event::GamepadButtonDown(index, _) | Event::GamepadButtonUp(index, _) => Some(*index),
+            _ => None,
+        };
+        match event.button() {
+            Some((code, true)) => {
+                if !self.held.contains_key(&code) {
+                    self.presses += 1;
+                    self.pressed.insert(code.clone());
+                }
+                let press = self.presses;
+                self.held
+                    .entry(code)
+                    .or_insert_with(|| Held {
+                        press,
+                        sources: HashSet::new(),
+                    })
+                    .sources
+                    .insert(source);
+            }
+            Some((code, false)) => {
+                let Some(held) = self.held.get_mut(&code) else {
+                    return;
+                };
+                held.sources.remove(&source);
+                if held.sources.is_empty() {
+                    self.held.remove(&code);
+                    self.released.insert(code);
+                }
+            }
+            None => {}
+        }
+    }
+
+    pub fn is_held(&self, code: &str) -> bool {
+        self.held.contains_key(code)
+    }
+
+    pub fn was_pressed(&self, code: &str) -> bool {
+        self.pressed.contains(code)
+    }
+
+    pub fn was_released(&self, code: &str) -> bool {
+        self.released.contains(code)
+    }
+
+    // An action counts as one button: pressing a second key bound to it while
+    // the first is held is not a new press, and it is released only once the
+    // last of its keys goes up.
+    pub fn is_action_held<A: Copy + Ord>(&self, bindings: &KeyBindings<A>, action: A) -> bool {
+        bindings.codes(action).iter().any(|code| self.is_held(code))
+    }
+
+    // Orders held actions by when they were pressed, later presses comparing
+    // greater. `None` when none of the action's keys is held.
+    pub fn action_held_since<A: Copy + Ord>(
+        &self,
+        bindings: &KeyBindings<A>,
+        action: A,
+    ) -> Option<u64> {
+        bindings
+            .codes(action)
+            .iter()
+            .filter_map(|code| self.held.get(code).map(|held| held.press))
+            .max()
+    }
+
+    pub fn was_action_pressed<A: Copy + Ord>(&self, bindings: &KeyBindings<A>, action: A) -> bool {
+        let codes = bindings.codes(action);
+        codes.iter().any(|code| self.was_pressed(code))
+            && !codes.iter().any(|code| self.previous.contains(code))
+    }
+
+    pub fn was_action_released<A: Copy + Ord>(&self, bindings: &KeyBindings<A>, action: A) -> bool {
+        let codes = bindings.codes(action);
+        codes.iter().any(|code| self.was_released(code)) && !self.is_action_held(bindings, action)
+    }
+}
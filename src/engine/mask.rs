@@ -0,0 +1,131 @@
+use super::geometry::{Point, Rect};
+use super::image::Bitmap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mask {
+    width: u32,
+    height: u32,
+    bits: Vec<bool>,
+}
+
+impl Mask {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            bits: vec![false; (width * height) as usize],
+        }
+    }
+
+    pub fn from_fn(width: u32, height: u32, f: impl Fn(u32, u32) -> bool) -> Self {
+        let mut mask = Self::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                mask.bits[(y * width + x) as usize] = f(x, y);
+            }
+        }
+        mask
+    }
+
+    // Rows of equal length where '#' marks an occupied pixel.
+    pub fn from_pattern(rows: &[&str]) -> Self {
+        let height = rows.len() as u32;
+        let width = rows.first().map_or(0, |row| row.len()) as u32;
+        Self::from_fn(width, height, |x, y| {
+            rows[y as usize].as_bytes().get(x as usize) == Some(&b'#')
+        })
+    }
+
+    pub fn from_bitmap(bitmap: &Bitmap) -> Self {
+        Self::from_fn(bitmap.width(), bitmap.height(), |x, y| {
+            bitmap.pixel(x, y).is_some_and(|pixel| pixel[3] != 0)
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> bool {
+        self.offset(x, y).is_some_and(|offset| self.bits[offset])
+    }
+
+    pub fn set(&mut self, x: i32, y: i32, value: bool) {
+        if let Some(offset) = self.offset(x, y) {
+            self.bits[offset] = value;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.bits.iter().any(|bit| *bit)
+    }
+
+    pub fn count(&self) -> usize {
+        self.bits.iter().filter(|bit| **bit).count()
+    }
+
+    pub fn overlaps(&self, rect: &Rect) -> bool {
+        self.topmost_hit(rect).is_some()
+    }
+
+    pub fn topmost_hit(&self, rect: &Rect) -> Option<Point> {
+        self.first_hit(rect, rect.top()..rect.bottom())
+    }
+
+    pub fn bottommost_hit(&self, rect: &Rect) -> Option<Point> {
+        self.first_hit(rect, (rect.top()..rect.bottom()).rev())
+    }
+
+    // Clears every pixel covered by an occupied pixel of `stamp` placed at `origin`.
+    pub fn erase(&mut self, stamp: &Mask, origin: &Point) {
+        for y in 0..stamp.height as i32 {
+            for x in 0..stamp.width as i32 {
+                if stamp.get(x, y) {
+                    self.set(origin.x as i32 + x, origin.y as i32 + y, false);
+                }
+            }
+        }
+    }
+
+    pub fn clear_rect(&mut self, rect: &Rect) {
+        for y in rect.top()..rect.bottom() {
+            for x in rect.left()..rect.right() {
+                self.set(x.into(), y.into(), false);
+            }
+        }
+    }
+
+    pub fn to_bitmap(&self, color: [u8; 4]) -> Bitmap {
+        let mut bitmap = Bitmap::new(self.width, self.height);
+        for y in 0..self.height as i32 {
+            for x in 0..self.width as i32 {
+                if self.get(x, y) {
+                    bitmap.set_pixel(x, y, color);
+                }
+            }
+        }
+        bitmap
+    }
+
+    fn first_hit(&self, rect: &Rect, rows: impl Iterator<Item = i16>) -> Option<Point> {
+        for y in rows {
+            for x in rect.left()..rect.right() {
+                if self.get(x.into(), y.into()) {
+                    return Some(Point { x, y });
+                }
+            }
+        }
+        None
+    }
+
+    fn offset(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || self.width as i32 <= x || self.height as i32 <= y {
+            return None;
+        }
+        Some((y as u32 * self.width + x as u32) as usize)
+    }
+}
@@ -0,0 +1,37 @@
+use std::ops::Range;
+
+use rand::{Rng as _, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// A named algorithm rather than `StdRng`, whose output may change between
+// rand releases and would break replays recorded with an older build.
+#[derive(Clone)]
+pub struct Rng {
+    seed: u64,
+    inner: ChaCha8Rng,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            inner: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn next_f32(&mut self) -> f32 {
+        self.inner.gen()
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.inner.next_u64()
+    }
+
+    pub fn gen_range(&mut self, range: Range<u32>) -> u32 {
+        self.inner.gen_range(range)
+    }
+}
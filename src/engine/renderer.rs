@@ -1,30 +1,83 @@
-use anyhow::Result;
-use wasm_bindgen::JsValue;
-use web_sys::{CanvasRenderingContext2d, HtmlImageElement};
+use std::cell::{Ref, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+use anyhow::{anyhow, Result};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 
 use super::browser;
 use super::geometry::Rect;
+use super::image::{Bitmap, Image};
 
 pub trait Renderer {
     fn clear(&self, rect: &Rect);
-    fn draw_image(&self, image: &HtmlImageElement, frame: &Rect, destination: &Rect);
+    fn draw_image(&self, image: &Image, frame: &Rect, destination: &Rect);
     fn draw_rect(&self, rect: &Rect);
+    fn fill_rect(&self, rect: &Rect, color: [u8; 4]);
 }
 
 pub struct CanvasRenderer {
     context: CanvasRenderingContext2d,
+    // Bitmaps are never changed behind their `Rc`, so each one is uploaded to
+    // its own canvas once and drawn from there until it is dropped.
+    bitmaps: RefCell<HashMap<*const Bitmap, (Weak<Bitmap>, HtmlCanvasElement)>>,
 }
 
 impl CanvasRenderer {
     pub fn new() -> Result<Self> {
         let context = browser::context()?;
-        Ok(Self { context })
+        Ok(Self {
+            context,
+            bitmaps: RefCell::new(HashMap::new()),
+        })
+    }
+
+    // put_image_data ignores transforms and alpha blending, so bitmaps are
+    // staged on an offscreen canvas and drawn from there like any image.
+    fn bitmap_canvas(&self, bitmap: &Rc<Bitmap>) -> Result<HtmlCanvasElement> {
+        let key = Rc::as_ptr(bitmap);
+        let mut bitmaps = self.bitmaps.borrow_mut();
+        if let Some((uploaded, canvas)) = bitmaps.get(&key) {
+            if uploaded.strong_count() > 0 {
+                return Ok(canvas.clone());
+            }
+        }
+        bitmaps.retain(|_, (uploaded, _)| uploaded.strong_count() > 0);
+
+        let canvas = browser::new_canvas()?;
+        let context = browser::context_2d(&canvas)?;
+        canvas.set_width(bitmap.width());
+        canvas.set_height(bitmap.height());
+        let image_data = browser::new_image_data(bitmap.pixels(), bitmap.width(), bitmap.height())?;
+        context
+            .put_image_data(&image_data, 0.0, 0.0)
+            .map_err(|err| anyhow!("Could not put image data {:#?}", err))?;
+
+        bitmaps.insert(key, (Rc::downgrade(bitmap), canvas.clone()));
+        Ok(canvas)
+    }
+
+    fn draw_bitmap(&self, bitmap: &Rc<Bitmap>, source: &Rect, destination: &Rect) -> Result<()> {
+        let canvas = self.bitmap_canvas(bitmap)?;
+        self.context
+            .draw_image_with_html_canvas_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
+                &canvas,
+                source.x().into(),
+                source.y().into(),
+                source.width().into(),
+                source.height().into(),
+                destination.x().into(),
+                destination.y().into(),
+                destination.width().into(),
+                destination.height().into(),
+            )
+            .map_err(|err| anyhow!("Could not draw bitmap {:#?}", err))
     }
 }
 
 impl Renderer for CanvasRenderer {
     fn clear(&self, rect: &Rect) {
-        self.context.set_fill_style(&JsValue::from_str("black"));
+        self.context.set_fill_style_str("black");
         self.context.fill_rect(
             rect.x().into(),
             rect.y().into(),
@@ -33,7 +86,16 @@ impl Renderer for CanvasRenderer {
         );
     }
 
-    fn draw_image(&self, image: &HtmlImageElement, source: &Rect, destination: &Rect) {
+    fn draw_image(&self, image: &Image, source: &Rect, destination: &Rect) {
+        let image = match image {
+            Image::Element(element) => element,
+            Image::Bitmap(bitmap) => {
+                self.draw_bitmap(bitmap, source, destination)
+                    .expect("Drawing is throwing exceptions! Unrecoverable error.");
+                return;
+            }
+        };
+
         self.context
             .draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
                 image,
@@ -57,4 +119,108 @@ impl Renderer for CanvasRenderer {
             rect.height().into(),
         );
     }
+
+    fn fill_rect(&self, rect: &Rect, color: [u8; 4]) {
+        let [r, g, b, a] = color;
+        self.context.set_fill_style_str(&format!(
+            "rgba({}, {}, {}, {})",
+            r,
+            g,
+            b,
+            a as f64 / 255.0
+        ));
+        self.context.fill_rect(
+            rect.x().into(),
+            rect.y().into(),
+            rect.width().into(),
+            rect.height().into(),
+        );
+    }
+}
+
+pub struct SoftwareRenderer {
+    target: RefCell<Bitmap>,
+}
+
+impl SoftwareRenderer {
+    const BLACK: [u8; 4] = [0, 0, 0, 0xff];
+
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            target: RefCell::new(Bitmap::new(width, height)),
+        }
+    }
+
+    pub fn bitmap(&self) -> Ref<'_, Bitmap> {
+        self.target.borrow()
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        self.target.borrow().to_png()
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn clear(&self, rect: &Rect) {
+        let mut target = self.target.borrow_mut();
+        for y in rect.top()..rect.bottom() {
+            for x in rect.left()..rect.right() {
+                target.set_pixel(x.into(), y.into(), Self::BLACK);
+            }
+        }
+    }
+
+    fn draw_image(&self, image: &Image, source: &Rect, destination: &Rect) {
+        // Elements can't be read back outside the browser.
+        let Some(image) = image.bitmap() else {
+            return;
+        };
+        if destination.width() <= 0 || destination.height() <= 0 {
+            return;
+        }
+
+        let mut target = self.target.borrow_mut();
+        for dy in 0..destination.height() {
+            let sy = source.y() as i32
+                + dy as i32 * source.height() as i32 / destination.height() as i32;
+            for dx in 0..destination.width() {
+                let sx = source.x() as i32
+                    + dx as i32 * source.width() as i32 / destination.width() as i32;
+                if sx < 0 || sy < 0 {
+                    continue;
+                }
+                if let Some(color) = image.pixel(sx as u32, sy as u32) {
+                    target.blend_pixel(
+                        (destination.x() + dx).into(),
+                        (destination.y() + dy).into(),
+                        color,
+                    );
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&self, rect: &Rect) {
+        // Mirrors the canvas default stroke style.
+        let mut target = self.target.borrow_mut();
+        let (left, top) = (rect.left() as i32, rect.top() as i32);
+        let (right, bottom) = (rect.right() as i32 - 1, rect.bottom() as i32 - 1);
+        for x in left..=right {
+            target.set_pixel(x, top, Self::BLACK);
+            target.set_pixel(x, bottom, Self::BLACK);
+        }
+        for y in top..=bottom {
+            target.set_pixel(left, y, Self::BLACK);
+            target.set_pixel(right, y, Self::BLACK);
+        }
+    }
+
+    fn fill_rect(&self, rect: &Rect, color: [u8; 4]) {
+        let mut target = self.target.borrow_mut();
+        for y in rect.top()..rect.bottom() {
+            for x in rect.left()..rect.right() {
+                target.blend_pixel(x.into(), y.into(), color);
+            }
+        }
+    }
 }
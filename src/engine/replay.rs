@@ -0,0 +1,170 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::event::{Event, EventSource};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub frame: u64,
+    pub timestamp: f64,
+    pub event: Event,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: Option<u64>,
+    // The game's own configuration, serialized by the game.
+    pub config: Option<String>,
+    pub events: Vec<RecordedEvent>,
+    // The delta of every update, indexed by frame.
+    pub deltas: Vec<f64>,
+}
+
+impl Replay {
+    pub const VERSION: u32 = 3;
+
+    pub fn new(seed: Option<u64>) -> Self {
+        Self {
+            version: Self::VERSION,
+            seed,
+            config: None,
+            events: vec![],
+            deltas: vec![],
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|err| anyhow!("Could not serialize replay {:#?}", err))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        let replay: Self = serde_json::from_str(json)
+            .map_err(|err| anyhow!("Could not deserialize replay {:#?}", err))?;
+        replay.validate()
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        bincode::serialize(self).map_err(|err| anyhow!("Could not serialize replay {:#?}", err))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let replay: Self = bincode::deserialize(bytes)
+            .map_err(|err| anyhow!("Could not deserialize replay {:#?}", err))?;
+        replay.validate()
+    }
+
+    fn validate(self) -> Result<Self> {
+        if self.version != Self::VERSION {
+            return Err(anyhow!("Unsupported replay version {}", self.version));
+        }
+        Ok(self)
+    }
+}
+
+pub struct RecordingEventSource<E: EventSource> {
+    inner: E,
+    replay: Rc<RefCell<Replay>>,
+    frame: u64,
+    timestamp: f64,
+}
+
+impl<E: EventSource> RecordingEventSource<E> {
+    pub fn new(inner: E, seed: Option<u64>) -> Self {
+        Self {
+            inner,
+            replay: Rc::new(RefCell::new(Replay::new(seed))),
+            frame: 0,
+            timestamp: 0.0,
+        }
+    }
+
+    pub fn with_config(self, config: String) -> Self {
+        self.replay.borrow_mut().config = Some(config);
+        self
+    }
+
+    pub fn replay(&self) -> Rc<RefCell<Replay>> {
+        self.replay.clone()
+    }
+}
+
+impl<E: EventSource> EventSource for RecordingEventSource<E> {
+    fn try_next(&mut self) -> Option<Event> {
+        let event = self.inner.try_next()?;
+        self.replay.borrow_mut().events.push(RecordedEvent {
+            frame: self.frame,
+            timestamp: self.timestamp,
+            event: event.clone(),
+        });
+        Some(event)
+    }
+
+    fn begin_update(&mut self, frame: u64, timestamp: f64) {
+        self.frame = frame;
+        self.timestamp = timestamp;
+        self.inner.begin_update(frame, timestamp);
+    }
+
+    fn update_delta(&mut self, delta: f64) -> f64 {
+        let delta = self.inner.update_delta(delta);
+        self.replay.borrow_mut().deltas.push(delta);
+        delta
+    }
+}
+
+// Feeds back the recorded events and frame times, so playback matches the
+// recording whatever the loop's timestep or frame rate.
+pub struct ReplayEventSource {
+    events: VecDeque<RecordedEvent>,
+    pending: VecDeque<Event>,
+    deltas: Vec<f64>,
+    frame: u64,
+}
+
+impl ReplayEventSource {
+    pub fn new(replay: Replay) -> Self {
+        let mut events = replay.events;
+        events.sort_by_key(|recorded| recorded.frame);
+
+        Self {
+            events: events.into(),
+            pending: VecDeque::new(),
+            deltas: replay.deltas,
+            frame: 0,
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.events.is_empty() && self.pending.is_empty()
+    }
+}
+
+impl EventSource for ReplayEventSource {
+    fn try_next(&mut self) -> Option<Event> {
+        self.pending.pop_front()
+    }
+
+    fn begin_update(&mut self, frame: u64, _timestamp: f64) {
+        self.frame = frame;
+        while let Some(recorded) = self.events.front() {
+            if frame < recorded.frame {
+                break;
+            }
+            if let Some(recorded) = self.events.pop_front() {
+                self.pending.push_back(recorded.event);
+            }
+        }
+    }
+
+    fn update_delta(&mut self, delta: f64) -> f64 {
+        self.deltas
+            .get(self.frame as usize)
+            .copied()
+            .unwrap_or(delta)
+    }
+}
@@ -0,0 +1,72 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+
+use super::browser;
+use super::clock::{Clock, ManualClock};
+
+pub type FrameCallback = Box<dyn FnMut(f64) -> Result<()>>;
+
+type SharedLoopClosure = Rc<RefCell<Option<browser::LoopClosure>>>;
+
+pub trait FrameScheduler {
+    fn start(&self, frame: FrameCallback) -> Result<()>;
+}
+
+pub struct AnimationFrameScheduler;
+
+impl FrameScheduler for AnimationFrameScheduler {
+    fn start(&self, mut frame: FrameCallback) -> Result<()> {
+        let f: SharedLoopClosure = Rc::new(RefCell::new(None));
+        let g = f.clone();
+        *g.borrow_mut() = Some(browser::create_raf_closure(move |perf: f64| {
+            frame(perf).expect("Failed to update");
+
+            browser::request_animation_frame(f.borrow().as_ref().unwrap()).expect("Loop failed");
+        }));
+
+        browser::request_animation_frame(
+            g.borrow()
+                .as_ref()
+                .ok_or_else(|| anyhow!("GameLoop: Loop is None"))?,
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ManualScheduler {
+    clock: ManualClock,
+    frame: Rc<RefCell<Option<FrameCallback>>>,
+}
+
+impl ManualScheduler {
+    pub fn new(clock: ManualClock) -> Self {
+        Self {
+            clock,
+            frame: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    pub fn step(&self, timestamp: f64) -> Result<()> {
+        self.clock.set(timestamp);
+
+        let mut frame = self.frame.borrow_mut();
+        let frame = frame
+            .as_mut()
+            .ok_or_else(|| anyhow!("ManualScheduler: Loop is not started"))?;
+        frame(timestamp)
+    }
+
+    pub fn advance(&self, delta: f64) -> Result<()> {
+        self.step(self.clock.now()? + delta)
+    }
+}
+
+impl FrameScheduler for ManualScheduler {
+    fn start(&self, frame: FrameCallback) -> Result<()> {
+        *self.frame.borrow_mut() = Some(frame);
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@
 pub struct Frame {
     cell_name: String,
     duration: f32,
+    marker: Option<String>,
 }
 
 impl Frame {
@@ -9,8 +10,22 @@ impl Frame {
         Self {
             cell_name,
             duration,
+            marker: None,
         }
     }
+
+    pub fn with_marker(mut self, marker: &str) -> Self {
+        self.marker = Some(marker.to_string());
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaybackMode {
+    Loop,
+    Once,
+    PingPong,
+    LoopTimes(u32),
 }
 
 #[derive(Clone)]
@@ -18,6 +33,13 @@ pub struct Sequence {
     frames: Vec<Frame>,
     acc_time: f32,
     current_key_point_index: usize,
+    mode: PlaybackMode,
+    speed: f32,
+    reversed: bool,
+    loops: u32,
+    started: bool,
+    finished: bool,
+    fired_markers: Vec<String>,
 }
 
 impl Sequence {
@@ -26,22 +48,140 @@ impl Sequence {
             frames,
             acc_time: 0.0,
             current_key_point_index: 0,
+            mode: PlaybackMode::Loop,
+            speed: 1.0,
+            reversed: false,
+            loops: 0,
+            started: false,
+            finished: false,
+            fired_markers: vec![],
         }
     }
 
+    pub fn with_mode(mut self, mode: PlaybackMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn reset(&mut self) {
+        self.acc_time = 0.0;
+        self.current_key_point_index = 0;
+        self.reversed = false;
+        self.loops = 0;
+        self.started = false;
+        self.finished = false;
+        self.fired_markers.clear();
+    }
+
     pub fn update(&mut self, delta: f32) {
-        self.acc_time += delta;
-        if self.frames[self.current_key_point_index].duration < self.acc_time {
-            self.acc_time -= self.frames[self.current_key_point_index].duration;
-            self.current_key_point_index += 1;
+        self.fired_markers.clear();
+        if self.finished || self.frames.is_empty() {
+            return;
+        }
+
+        if !self.started {
+            self.started = true;
+            self.fire_marker();
+        }
+
+        // A zero-length sequence would never run out of time to consume.
+        if self.frames.iter().all(|frame| frame.duration <= 0.0) {
+            return;
         }
 
-        if self.frames.len() <= self.current_key_point_index {
-            self.current_key_point_index = 0;
+        self.acc_time += delta * self.speed;
+        while self.frames[self.current_key_point_index].duration < self.acc_time {
+            self.acc_time -= self.frames[self.current_key_point_index].duration;
+            if !self.advance() {
+                self.acc_time = 0.0;
+                self.finished = true;
+                return;
+            }
+            self.fire_marker();
         }
     }
 
     pub fn current_frame_cell_name(&self) -> &str {
         &self.frames[self.current_key_point_index].cell_name
     }
+
+    pub fn current_frame_index(&self) -> usize {
+        self.current_key_point_index
+    }
+
+    // Markers of every frame entered during the last update, in order.
+    pub fn fired_markers(&self) -> &[String] {
+        &self.fired_markers
+    }
+
+    fn advance(&mut self) -> bool {
+        let last = self.frames.len() - 1;
+        let index = self.current_key_point_index;
+
+        self.current_key_point_index = match self.mode {
+            PlaybackMode::Loop => {
+                if index < last {
+                    index + 1
+                } else {
+                    0
+                }
+            }
+            PlaybackMode::Once => {
+                if index < last {
+                    index + 1
+                } else {
+                    return false;
+                }
+            }
+            PlaybackMode::LoopTimes(times) => {
+                if index < last {
+                    index + 1
+                } else {
+                    self.loops += 1;
+                    if times <= self.loops {
+                        return false;
+                    }
+                    0
+                }
+            }
+            PlaybackMode::PingPong => {
+                if last == 0 {
+                    0
+                } else {
+                    if (self.reversed && index == 0) || (!self.reversed && index == last) {
+                        self.reversed = !self.reversed;
+                    }
+                    if self.reversed {
+                        index - 1
+                    } else {
+                        index + 1
+                    }
+                }
+            }
+        };
+        true
+    }
+
+    fn fire_marker(&mut self) {
+        if let Some(marker) = &self.frames[self.current_key_point_index].marker {
+            self.fired_markers.push(marker.clone());
+        }
+    }
 }
@@ -0,0 +1,52 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+
+use super::browser;
+
+pub trait SettingsStore {
+    fn load(&self, key: &str) -> Result<Option<String>>;
+    fn save(&self, key: &str, value: &str) -> Result<()>;
+}
+
+pub struct LocalStorageSettings;
+
+impl SettingsStore for LocalStorageSettings {
+    fn load(&self, key: &str) -> Result<Option<String>> {
+        browser::local_storage()?
+            .get_item(key)
+            .map_err(|err| anyhow!("Could not read setting {} {:#?}", key, err))
+    }
+
+    fn save(&self, key: &str, value: &str) -> Result<()> {
+        browser::local_storage()?
+            .set_item(key, value)
+            .map_err(|err| anyhow!("Could not write setting {} {:#?}", key, err))
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct MemorySettings {
+    values: Rc<RefCell<HashMap<String, String>>>,
+}
+
+impl MemorySettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SettingsStore for MemorySettings {
+    fn load(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.values.borrow().get(key).cloned())
+    }
+
+    fn save(&self, key: &str, value: &str) -> Result<()> {
+        self.values
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+}
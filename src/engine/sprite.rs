@@ -12,6 +12,7 @@ use web_sys::HtmlImageElement;
 
 use super::browser;
 use super::geometry::{Point, Rect, Shape};
+use super::image::{Bitmap, Image};
 use super::renderer::Renderer;
 
 #[derive(Deserialize, Clone)]
@@ -50,15 +51,29 @@ struct Sheet {
 
 pub struct SpriteSheet {
     sheet: Sheet,
-    image: HtmlImageElement,
+    image: Image,
 }
 
 impl SpriteSheet {
     pub async fn load(json_path: &str, png_path: &str) -> Result<SpriteSheet> {
         let json = browser::fetch_json(json_path).await?;
-        let sheet: Sheet = json.into_serde()?;
+        let sheet: Sheet = serde_wasm_bindgen::from_value(json)?;
         let image = load_image_element(png_path).await?;
 
+        Ok(SpriteSheet::new(sheet, image.into()))
+    }
+
+    pub fn load_from_files(json_path: &str, png_path: &str) -> Result<SpriteSheet> {
+        let json = std::fs::read_to_string(json_path)
+            .map_err(|err| anyhow!("Could not read {}: {:#?}", json_path, err))?;
+        let png = std::fs::read(png_path)
+            .map_err(|err| anyhow!("Could not read {}: {:#?}", png_path, err))?;
+
+        Self::from_json(&json, Bitmap::from_png(&png)?.into())
+    }
+
+    pub fn from_json(json: &str, image: Image) -> Result<SpriteSheet> {
+        let sheet: Sheet = serde_json::from_str(json)?;
         Ok(SpriteSheet::new(sheet, image))
     }
 
@@ -73,7 +88,27 @@ impl SpriteSheet {
         renderer.draw_image(&self.image, &source, &destination)
     }
 
-    fn new(sheet: Sheet, image: HtmlImageElement) -> Self {
+    pub fn draw_scaled(
+        &self,
+        renderer: &dyn Renderer,
+        cell: &Cell,
+        destination: &Point,
+        scale: i16,
+    ) {
+        let source: Rect = cell.clone().frame.into();
+        let shape = cell.shape();
+        let destination = Rect::new(
+            *destination,
+            Shape {
+                width: shape.width * scale,
+                height: shape.height * scale,
+            },
+        );
+
+        renderer.draw_image(&self.image, &source, &destination)
+    }
+
+    fn new(sheet: Sheet, image: Image) -> Self {
         Self { sheet, image }
     }
 }
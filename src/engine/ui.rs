@@ -1,10 +1,10 @@
-use anyhow::{Result, anyhow};
+use anyhow::{anyhow, Result};
 use futures::channel::mpsc::unbounded;
 use futures::channel::mpsc::UnboundedReceiver;
-use web_sys::{HtmlElement, Element};
 use wasm_bindgen::prelude::*;
+use web_sys::{Element, HtmlElement};
 
-use super::browser::{document, closure_wrap};
+use super::browser::{closure_wrap, document};
 
 pub fn draw_ui(html: &str) -> Result<()> {
     find_ui()?
@@ -34,7 +34,7 @@ fn find_ui() -> Result<Element> {
 pub fn add_click_handler(elem: HtmlElement) -> UnboundedReceiver<()> {
     let (mut click_sender, click_receiver) = unbounded();
     let on_click = closure_wrap(Box::new(move || {
-        let _ =click_sender.start_send(());
+        let _ = click_sender.start_send(());
     }) as Box<dyn FnMut()>);
     elem.set_onclick(Some(on_click.as_ref().unchecked_ref()));
     on_click.forget();
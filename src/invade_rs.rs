@@ -3,40 +3,108 @@ use std::rc::Rc;
 use anyhow::Result;
 use async_trait::async_trait;
 
+use crate::engine::audio::{AudioBackend, NullAudioBackend};
+use crate::engine::browser;
 use crate::engine::event::Event;
 use crate::engine::geometry::Rect;
+use crate::engine::input::InputState;
 use crate::engine::renderer::Renderer;
+use crate::engine::settings::{MemorySettings, SettingsStore};
 use crate::engine::sprite;
 use crate::engine::sprite::SpriteSheet;
 use crate::engine::{DrawCommand, Game};
 
+use self::character::layers;
+use self::config::Config;
+use self::controls::Controls;
 use self::fsm::StateMachineRunner;
 use self::game_state::{out_game::OutGame, GameStateMachine};
+use self::progress::Progress;
+use self::score::Score;
+use self::sound::SoundBank;
 
 mod beam;
-mod character;
-mod ferris;
-mod fsm;
-mod game_state;
+pub mod character;
+pub mod config;
+pub mod controls;
+pub mod explosion;
+pub mod ferris;
+pub mod fsm;
+pub mod game_state;
+mod hud;
+mod mask_shield;
 mod missile;
-mod shield;
+mod mouse_controls;
+pub mod progress;
+pub mod score;
+pub mod shield;
 mod ship;
-mod turbo_fish;
+pub mod sound;
+mod touch_controls;
+pub mod turbo_fish;
 mod wall;
 
 const SCREEN_RECT: Rect = Rect::new_from_x_y_w_h(0, 0, 600, 600);
 
 pub struct InvadeRs {
     runner: StateMachineRunner<Event, GameStateMachine>,
+    seed: u64,
+    config: Config,
+    sprite_sheet: Option<Rc<SpriteSheet>>,
+    audio: Rc<dyn AudioBackend>,
+    settings: Rc<dyn SettingsStore>,
+    controls: Option<Rc<Controls>>,
 }
 
 impl InvadeRs {
     pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
         Self {
             runner: StateMachineRunner::new(),
+            seed,
+            config: Config::default(),
+            sprite_sheet: None,
+            audio: Rc::new(NullAudioBackend::default()),
+            settings: Rc::new(MemorySettings::new()),
+            controls: None,
         }
     }
 
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_sprite_sheet(mut self, sprite_sheet: SpriteSheet) -> Self {
+        self.sprite_sheet = Some(Rc::new(sprite_sheet));
+        self
+    }
+
+    pub fn with_audio(mut self, audio: Rc<dyn AudioBackend>) -> Self {
+        self.audio = audio;
+        self
+    }
+
+    pub fn with_settings(mut self, settings: Rc<dyn SettingsStore>) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.runner.states().rev().find_map(GameStateMachine::seed)
+    }
+
+    pub fn lives(&self) -> Option<u32> {
+        self.runner.states().rev().find_map(GameStateMachine::lives)
+    }
+
+    pub fn score(&self) -> Option<Score> {
+        self.runner.states().rev().find_map(GameStateMachine::score)
+    }
+
     async fn load_sprite_sheet(&mut self) -> Result<Rc<SpriteSheet>> {
         sprite::SpriteSheet::load("texture.json", "texture.png")
             .await
@@ -53,26 +121,52 @@ impl Default for InvadeRs {
 #[async_trait(?Send)]
 impl Game for InvadeRs {
     async fn initialize(&mut self) -> Result<()> {
-        let sprite_sheet = self.load_sprite_sheet().await?;
+        let sprite_sheet = match self.sprite_sheet.clone() {
+            Some(sprite_sheet) => sprite_sheet,
+            None => self.load_sprite_sheet().await?,
+        };
 
-        self.runner.transition(OutGame::new(sprite_sheet).into())?;
+        let sounds = Rc::new(SoundBank::new(self.audio.clone()));
+        let controls = Controls::load(self.settings.clone()).unwrap_or_else(|err| {
+            browser::log(&format!("Using default key bindings: {:#?}", err));
+            Controls::new(self.settings.clone())
+        });
+        let controls = Rc::new(controls);
+        self.controls = Some(controls.clone());
+        let progress = Progress::new(&self.config, Score::default());
+        let out_game = OutGame::new(
+            sprite_sheet,
+            sounds,
+            controls,
+            self.config,
+            self.seed,
+            progress,
+        )
+        .with_start_prompt();
+        self.runner.replace(out_game.into())?;
         Ok(())
     }
 
-    fn update(&mut self, delta: f32, events: &[Event]) -> Result<()> {
-        self.runner.update(delta, events)
+    fn update(&mut self, delta: f32, input: &InputState, events: &[Event]) -> Result<()> {
+        if let Some(controls) = &self.controls {
+            controls.update_pointers(events);
+        }
+        self.runner.update(delta, input, events)
     }
 
-    fn draw(&self) -> Vec<DrawCommand> {
+    fn draw(&self, alpha: f32) -> Vec<DrawCommand> {
         let clear_command = DrawCommand(
-            0,
+            layers::BACKGROUND,
             Box::new(|renderer: &dyn Renderer| {
                 renderer.clear(&SCREEN_RECT);
             }),
         );
 
         let mut draw_commands = vec![clear_command];
-        draw_commands.append(self.runner.state.draw().as_mut());
+        // Every state on the stack is drawn so that menus overlay the game.
+        for state in self.runner.states() {
+            draw_commands.append(state.draw(alpha).as_mut());
+        }
         draw_commands
     }
 }
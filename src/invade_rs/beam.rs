@@ -2,6 +2,7 @@ use std::rc::Rc;
 
 use crate::engine::geometry::Rect;
 use crate::engine::geometry::{Point, Shape};
+use crate::engine::random::Rng;
 use crate::engine::sprite::{Cell, SpriteSheet};
 use crate::engine::DrawCommand;
 
@@ -27,6 +28,7 @@ impl From<BeamColor> for &str {
 pub struct Beam {
     id: Id,
     position: Point,
+    last_position: Point,
     sprite_sheet: Rc<SpriteSheet>,
     cell: Cell,
     velocity: f32,
@@ -51,6 +53,7 @@ impl Beam {
         Self {
             id: Id::new(),
             position,
+            last_position: position,
             sprite_sheet,
             cell,
             velocity: Self::DEFAULT_VELOCITY,
@@ -73,16 +76,17 @@ impl Beam {
         Rect::new(position, shape)
     }
 
-    pub fn update(&mut self, delta_ms: f32) -> Option<GameCommand> {
+    pub fn update(&mut self, delta_ms: f32, _rng: &mut Rng) -> Option<GameCommand> {
+        self.last_position = self.position;
         self.position.y += (self.velocity * delta_ms).round() as i16;
 
         None
     }
 
-    pub fn draw(&self) -> Option<DrawCommand> {
+    pub fn draw(&self, alpha: f32) -> Option<DrawCommand> {
         let cell = self.cell.clone();
         let sprite_sheet = self.sprite_sheet.clone();
-        let position = self.position;
+        let position = self.last_position.lerp(&self.position, alpha);
 
         Some(DrawCommand(
             layers::BEAM,
@@ -93,14 +97,14 @@ impl Beam {
     }
 
     pub fn on_exit_screen(&mut self) -> Option<GameCommand> {
-        Some(GameCommand::DestroyCharacter(self.id().clone()))
+        Some(GameCommand::ExplodeCharacter(self.id().clone()))
     }
 
     pub fn on_collide(&self, other: &GameCharacter) -> Option<GameCommand> {
         match other {
-            GameCharacter::ShieldElement(_) | GameCharacter::Ship(_) => {
-                Some(GameCommand::DestroyCharacter(self.id().clone()))
-            }
+            GameCharacter::ShieldElement(_)
+            | GameCharacter::MaskShield(_)
+            | GameCharacter::Ship(_) => Some(GameCommand::ExplodeCharacter(self.id().clone())),
             _ => None,
         }
     }
@@ -1,13 +1,17 @@
 use uuid::Uuid;
 
 use super::beam::Beam;
+use super::explosion::Explosion;
 use super::ferris::Ferris;
+use super::mask_shield::{Crater, MaskShield};
 use super::missile::Missile;
 use super::shield::ShieldElement;
 use super::ship::Ship;
+use super::sound::SoundEffect;
 use super::turbo_fish::TurboFish;
 use super::wall::Wall;
 use crate::engine::geometry::Rect;
+use crate::engine::random::Rng;
 use crate::engine::DrawCommand;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -19,11 +23,25 @@ impl Id {
     }
 }
 
+impl Default for Id {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub enum GameCommand {
     SpawnCharacter(GameCharacter),
     DestroyCharacter(Id),
+    DestroyEnemy(Id, u32),
+    ExplodeCharacter(Id),
+    DamageShield(Id, u32),
+    ErodeShield(Id, Crater),
     DestroyPlayer,
+    RespawnPlayer,
+    LandFleet,
     TurnFerris,
+    SetFleetSpeed(f32),
+    PlaySound(SoundEffect),
 }
 
 pub enum GameCharacter {
@@ -33,6 +51,8 @@ pub enum GameCharacter {
     Missile(Missile),
     Beam(Beam),
     ShieldElement(ShieldElement),
+    MaskShield(MaskShield),
+    Explosion(Explosion),
     Wall(Wall),
 }
 
@@ -45,6 +65,8 @@ impl GameCharacter {
             Self::Missile(character) => character.id(),
             Self::Beam(character) => character.id(),
             Self::ShieldElement(character) => character.id(),
+            Self::MaskShield(character) => character.id(),
+            Self::Explosion(character) => character.id(),
             Self::Wall(character) => character.id(),
         }
     }
@@ -57,19 +79,36 @@ impl GameCharacter {
             Self::Missile(character) => character.bounding_box(),
             Self::Beam(character) => character.bounding_box(),
             Self::ShieldElement(character) => character.bounding_box(),
+            Self::MaskShield(character) => character.bounding_box(),
+            Self::Explosion(character) => character.bounding_box(),
             Self::Wall(character) => character.bounding_box(),
         }
     }
 
-    pub fn update(&mut self, delta: f32) -> Option<GameCommand> {
+    pub fn intersects(&self, other: &GameCharacter) -> bool {
+        match (self, other) {
+            (Self::MaskShield(shield), other) | (other, Self::MaskShield(shield)) => {
+                shield.overlaps(&other.bounding_box())
+            }
+            _ => self.bounding_box().intersects(&other.bounding_box()),
+        }
+    }
+
+    pub fn is_shield(&self) -> bool {
+        matches!(self, Self::ShieldElement(_) | Self::MaskShield(_))
+    }
+
+    pub fn update(&mut self, delta: f32, rng: &mut Rng) -> Option<GameCommand> {
         match self {
-            Self::Ferris(character) => character.update(delta),
-            Self::Ship(character) => character.update(delta),
-            Self::TurboFish(character) => character.update(delta),
-            Self::Missile(character) => character.update(delta),
-            Self::Beam(character) => character.update(delta),
-            Self::ShieldElement(character) => character.update(delta),
-            Self::Wall(character) => character.update(delta),
+            Self::Ferris(character) => character.update(delta, rng),
+            Self::Ship(character) => character.update(delta, rng),
+            Self::TurboFish(character) => character.update(delta, rng),
+            Self::Missile(character) => character.update(delta, rng),
+            Self::Beam(character) => character.update(delta, rng),
+            Self::ShieldElement(character) => character.update(delta, rng),
+            Self::MaskShield(character) => character.update(delta, rng),
+            Self::Explosion(character) => character.update(delta, rng),
+            Self::Wall(character) => character.update(delta, rng),
         }
     }
 
@@ -81,6 +120,8 @@ impl GameCharacter {
             Self::Missile(character) => character.on_exit_screen(),
             Self::Beam(character) => character.on_exit_screen(),
             Self::ShieldElement(character) => character.on_exit_screen(),
+            Self::MaskShield(character) => character.on_exit_screen(),
+            Self::Explosion(character) => character.on_exit_screen(),
             Self::Wall(character) => character.on_exit_screen(),
         }
     }
@@ -93,19 +134,23 @@ impl GameCharacter {
             Self::Missile(character) => character.on_collide(other),
             Self::Beam(character) => character.on_collide(other),
             Self::ShieldElement(character) => character.on_collide(other),
+            Self::MaskShield(character) => character.on_collide(other),
+            Self::Explosion(character) => character.on_collide(other),
             Self::Wall(character) => character.on_collide(other),
         }
     }
 
-    pub fn draw(&self) -> Option<DrawCommand> {
+    pub fn draw(&self, alpha: f32) -> Option<DrawCommand> {
         match self {
-            Self::Ferris(character) => character.draw(),
-            Self::Ship(character) => character.draw(),
-            Self::TurboFish(character) => character.draw(),
-            Self::Missile(character) => character.draw(),
-            Self::Beam(character) => character.draw(),
-            Self::ShieldElement(character) => character.draw(),
-            Self::Wall(character) => character.draw(),
+            Self::Ferris(character) => character.draw(alpha),
+            Self::Ship(character) => character.draw(alpha),
+            Self::TurboFish(character) => character.draw(alpha),
+            Self::Missile(character) => character.draw(alpha),
+            Self::Beam(character) => character.draw(alpha),
+            Self::ShieldElement(character) => character.draw(alpha),
+            Self::MaskShield(character) => character.draw(alpha),
+            Self::Explosion(character) => character.draw(alpha),
+            Self::Wall(character) => character.draw(alpha),
         }
     }
 }
@@ -140,6 +185,18 @@ impl From<Beam> for GameCharacter {
     }
 }
 
+impl From<Explosion> for GameCharacter {
+    fn from(character: Explosion) -> Self {
+        Self::Explosion(character)
+    }
+}
+
+impl From<MaskShield> for GameCharacter {
+    fn from(character: MaskShield) -> Self {
+        Self::MaskShield(character)
+    }
+}
+
 impl From<ShieldElement> for GameCharacter {
     fn from(character: ShieldElement) -> Self {
         Self::ShieldElement(character)
@@ -159,4 +216,6 @@ pub mod layers {
     pub const MISSILE: u8 = 3;
     pub const SHIP: u8 = 4;
     pub const ENEMY: u8 = 5;
+    pub const EFFECT: u8 = 6;
+    pub const HUD: u8 = 7;
 }
@@ -0,0 +1,65 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    pub lives: u32,
+    // Shields carry their damage over between waves and are rebuilt every
+    // `shield_restore_interval` waves. Zero never rebuilds them.
+    pub shield_restore_interval: u32,
+    pub shield_style: ShieldStyle,
+    pub fleet_speed_curve: SpeedCurve,
+}
+
+impl Config {
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|err| anyhow!("Could not serialize config {:#?}", err))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|err| anyhow!("Could not deserialize config {:#?}", err))
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            lives: 3,
+            shield_restore_interval: 4,
+            shield_style: ShieldStyle::Tiles,
+            fleet_speed_curve: SpeedCurve::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ShieldStyle {
+    Tiles,
+    Mask,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpeedCurve {
+    pub max_multiplier: f32,
+    pub exponent: f32,
+}
+
+impl SpeedCurve {
+    pub fn multiplier(&self, survivors: usize, fleet_size: usize) -> f32 {
+        if fleet_size == 0 {
+            return 1.0;
+        }
+
+        let destroyed = (1.0 - survivors as f32 / fleet_size as f32).clamp(0.0, 1.0);
+        1.0 + (self.max_multiplier - 1.0) * destroyed.powf(self.exponent)
+    }
+}
+
+impl Default for SpeedCurve {
+    fn default() -> Self {
+        Self {
+            max_multiplier: 6.0,
+            exponent: 2.0,
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::cell::{Cell, Ref, RefCell};
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::event::Event;
+use crate::engine::input::KeyBindings;
+use crate::engine::settings::SettingsStore;
+
+use super::mouse_controls::MouseControls;
+use super::touch_controls::VirtualControls;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Fire,
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Action; 4] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Fire,
+        Action::Pause,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "LEFT",
+            Action::MoveRight => "RIGHT",
+            Action::Fire => "FIRE",
+            Action::Pause => "PAUSE",
+        }
+    }
+}
+
+pub type Bindings = KeyBindings<Action>;
+
+// How the ship is steered. Keys, pads and touch buttons work in either
+// scheme; `Mouse` additionally makes the ship follow the mouse and fire on
+// click.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlScheme {
+    #[default]
+    Keyboard,
+    Mouse,
+}
+
+pub fn default_bindings() -> Bindings {
+    Bindings::new()
+        .with(
+            Action::MoveLeft,
+            &["ArrowLeft", "KeyA", "PadDPadLeft", "PadStickLeft"],
+        )
+        .with(
+            Action::MoveRight,
+            &["ArrowRight", "KeyD", "PadDPadRight", "PadStickRight"],
+        )
+        .with(
+            Action::Fire,
+            &["Space", "KeyZ", "KeyX", "PadSouth", "PadWest"],
+        )
+        .with(Action::Pause, &["Escape", "KeyP", "PadStart"])
+}
+
+// The bitmap font only has letters and digits, so codes are shortened to
+// what is printed on a US keyboard or an Xbox style pad.
+pub fn key_label(code: &str) -> String {
+    let label = match code {
+        "Escape" => "ESC",
+        "Backspace" => "BKSP",
+        "ShiftLeft" | "ShiftRight" => "SHIFT",
+        "ControlLeft" | "ControlRight" => "CTRL",
+        "AltLeft" | "AltRight" => "ALT",
+        "PadSouth" => "PADA",
+        "PadEast" => "PADB",
+        "PadWest" => "PADX",
+        "PadNorth" => "PADY",
+        "PadSelect" => "SELECT",
+        "PadStart" => "START",
+        "PadDPadUp" => "DUP",
+        "PadDPadDown" => "DDOWN",
+        "PadDPadLeft" => "DLEFT",
+        "PadDPadRight" => "DRIGHT",
+        "PadStickUp" => "SUP",
+        "PadStickDown" => "SDOWN",
+        "PadStickLeft" => "SLEFT",
+        "PadStickRight" => "SRIGHT",
+        _ => code
+            .strip_prefix("Key")
+            .or_else(|| code.strip_prefix("Digit"))
+            .or_else(|| code.strip_prefix("Arrow"))
+            .unwrap_or(code),
+    };
+    label
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+pub struct Controls {
+    bindings: RefCell<Bindings>,
+    scheme: Cell<ControlScheme>,
+    touch: RefCell<VirtualControls>,
+    mouse: RefCell<MouseControls>,
+    settings: Rc<dyn SettingsStore>,
+}
+
+impl Controls {
+    const SETTINGS_KEY: &'static str = "invade-rs.bindings";
+    const SCHEME_SETTINGS_KEY: &'static str = "invade-rs.control-scheme";
+
+    pub fn new(settings: Rc<dyn SettingsStore>) -> Self {
+        Self {
+            bindings: RefCell::new(default_bindings()),
+            scheme: Cell::new(ControlScheme::default()),
+            touch: RefCell::new(VirtualControls::new()),
+            mouse: RefCell::new(MouseControls::new()),
+            settings,
+        }
+    }
+
+    // Actions missing from the stored table fall back to their defaults, so
+    // settings saved before an action existed still leave it playable.
+    pub fn load(settings: Rc<dyn SettingsStore>) -> Result<Self> {
+        let mut bindings = match settings.load(Self::SETTINGS_KEY)? {
+            Some(json) => Bindings::from_json(&json)?,
+            None => default_bindings(),
+        };
+        let defaults = default_bindings();
+        for action in Action::ALL {
+            if bindings.codes(action).is_empty() {
+                for code in defaults.codes(action) {
+                    if bindings.action(code).is_none() {
+                        bindings.bind(action, code);
+                    }
+                }
+            }
+        }
+
+        let scheme = match settings.load(Self::SCHEME_SETTINGS_KEY)? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|err| anyhow!("Could not deserialize control scheme {:#?}", err))?,
+            None => ControlScheme::default(),
+        };
+
+        let controls = Self::new(settings);
+        *controls.bindings.borrow_mut() = bindings;
+        controls.scheme.set(scheme);
+        Ok(controls)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let json = self.bindings.borrow().to_json()?;
+        self.settings.save(Self::SETTINGS_KEY, &json)?;
+        let json = serde_json::to_string(&self.scheme.get())
+            .map_err(|err| anyhow!("Could not serialize control scheme {:#?}", err))?;
+        self.settings.save(Self::SCHEME_SETTINGS_KEY, &json)
+    }
+
+    pub fn scheme(&self) -> ControlScheme {
+        self.scheme.get()
+    }
+
+    pub fn set_scheme(&self, scheme: ControlScheme) {
+        self.scheme.set(scheme);
+    }
+
+    pub fn mouse(&self) -> Ref<'_, MouseControls> {
+        self.mouse.borrow()
+    }
+
+    pub fn bindings(&self) -> Ref<'_, Bindings> {
+        self.bindings.borrow()
+    }
+
+    pub fn touch(&self) -> Ref<'_, VirtualControls> {
+        self.touch.borrow()
+    }
+
+    // Runs every frame whatever the game state, so a finger or button lifted
+    // while a menu is open is not left held.
+    pub fn update_pointers(&self, events: &[Event]) {
+        self.touch.borrow_mut().update(events);
+        self.mouse.borrow_mut().update(events);
+    }
+
+    pub fn bind(&self, action: Action, code: &str) {
+        let mut bindings = self.bindings.borrow_mut();
+        bindings.bind(action, code);
+        keep_pause_bound(&mut bindings);
+    }
+
+    pub fn clear(&self, action: Action) {
+        let mut bindings = self.bindings.borrow_mut();
+        bindings.clear(action);
+        keep_pause_bound(&mut bindings);
+    }
+}
+
+// Pause is the only way out of a running game, so taking its last key gives
+// it back whichever of its default keys are still free.
+fn keep_pause_bound(bindings: &mut Bindings) {
+    if !bindings.codes(Action::Pause).is_empty() {
+        return;
+    }
+    for code in default_bindings().codes(Action::Pause) {
+        if bindings.action(code).is_none() {
+            bindings.bind(Action::Pause, code);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::rc::Rc;
+
+use crate::engine::geometry::{Point, Rect, Shape};
+use crate::engine::random::Rng;
+use crate::engine::sequence::{Frame, PlaybackMode, Sequence};
+use crate::engine::sprite::{Cell, SpriteSheet};
+use crate::engine::DrawCommand;
+
+use super::character::{layers, GameCharacter, GameCommand, Id};
+use super::sound::SoundEffect;
+
+#[derive(Clone)]
+pub struct Explosion {
+    id: Id,
+    center: Point,
+    sprite_sheet: Rc<SpriteSheet>,
+    animation: Sequence,
+    sound: SoundEffect,
+}
+
+impl Explosion {
+    const FRAME_DURATION: f32 = 80.0;
+    const SCALE: i16 = 2;
+    const BLAST: &'static str = "blast";
+
+    pub fn new(sprite_sheet: Rc<SpriteSheet>, center: Point, sound: SoundEffect) -> Self {
+        let animation = Self::new_animation();
+
+        Self {
+            id: Id::new(),
+            center,
+            sprite_sheet,
+            animation,
+            sound,
+        }
+    }
+
+    fn new_animation() -> Sequence {
+        const EXPLOSION_ANIMATION_FRAMES: usize = 2;
+
+        // The blast is heard as the first frame shows up.
+        let frames = (0..EXPLOSION_ANIMATION_FRAMES).map(|i| {
+            let cell_name = format!("explode_{}.png", i);
+            let frame = Frame::new(cell_name, Self::FRAME_DURATION);
+            match i {
+                0 => frame.with_marker(Self::BLAST),
+                _ => frame,
+            }
+        });
+        Sequence::new(frames.collect()).with_mode(PlaybackMode::Once)
+    }
+
+    fn get_current_frame_cell(&self) -> Option<&Cell> {
+        self.sprite_sheet
+            .cell(self.animation.current_frame_cell_name())
+    }
+
+    pub fn id(&self) -> &Id {
+        &self.id
+    }
+
+    pub fn bounding_box(&self) -> Rect {
+        let shape = self
+            .get_current_frame_cell()
+            .expect("cell not found")
+            .shape();
+        let shape = Shape {
+            width: shape.width * Self::SCALE,
+            height: shape.height * Self::SCALE,
+        };
+        let position = Point {
+            x: self.center.x - shape.width / 2,
+            y: self.center.y - shape.height / 2,
+        };
+
+        Rect::new(position, shape)
+    }
+
+    pub fn update(&mut self, delta_ms: f32, _rng: &mut Rng) -> Option<GameCommand> {
+        self.animation.update(delta_ms);
+        if self.animation.finished() {
+            return Some(GameCommand::DestroyCharacter(self.id().clone()));
+        }
+        if self
+            .animation
+            .fired_markers()
+            .iter()
+            .any(|m| m == Self::BLAST)
+        {
+            return Some(GameCommand::PlaySound(self.sound));
+        }
+
+        None
+    }
+
+    pub fn draw(&self, _alpha: f32) -> Option<DrawCommand> {
+        let cell = self
+            .get_current_frame_cell()
+            .expect("cell not found")
+            .clone();
+        let sprite_sheet = self.sprite_sheet.clone();
+        let bounding_box = self.bounding_box();
+        let position = Point {
+            x: bounding_box.x(),
+            y: bounding_box.y(),
+        };
+
+        Some(DrawCommand(
+            layers::EFFECT,
+            Box::new(move |renderer| {
+                sprite_sheet.draw_scaled(renderer, &cell, &position, Self::SCALE);
+            }),
+        ))
+    }
+
+    pub fn on_exit_screen(&mut self) -> Option<GameCommand> {
+        Some(GameCommand::DestroyCharacter(self.id().clone()))
+    }
+
+    pub fn on_collide(&self, _other: &GameCharacter) -> Option<GameCommand> {
+        None
+    }
+}
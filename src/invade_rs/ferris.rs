@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
 use crate::engine::geometry::{Point, Rect, Shape};
+use crate::engine::random::Rng;
 use crate::engine::sequence::{Frame, Sequence};
 use crate::engine::sprite::{Cell, SpriteSheet};
 use crate::engine::DrawCommand;
@@ -35,6 +36,16 @@ impl From<FerrisColor> for &str {
     }
 }
 
+impl FerrisColor {
+    pub fn points(&self) -> u32 {
+        match self {
+            FerrisColor::Blue => 10,
+            FerrisColor::Green => 20,
+            FerrisColor::Magenta => 30,
+        }
+    }
+}
+
 impl From<FerrisColor> for BeamColor {
     fn from(val: FerrisColor) -> Self {
         match val {
@@ -49,15 +60,29 @@ impl From<FerrisColor> for BeamColor {
 pub struct Ferris {
     id: Id,
     position: Point,
+    last_position: Point,
+    // The part of a pixel moved but not yet drawn, so slow speeds and small
+    // speed-ups are not rounded away.
+    subpixel: (f32, f32),
     sprite_sheet: Rc<SpriteSheet>,
     animation: Sequence,
     color: FerrisColor,
     state: FerrisState,
+    resume_state: FerrisState,
+    velocity: f32,
+    speed_scale: f32,
+    spawn_beam_ratio: f32,
 }
 
 impl Ferris {
     const SPAWN_BEAM_RATIO: f32 = 0.00010;
     const DEFAULT_VELOCITY: f32 = 80.0 / 1000.0;
+    const ANIMATION_FRAMES: usize = 5;
+    const FRAME_DURATION: f32 = 150.0;
+    // One full walk cycle, the fleet's equivalent of a marching step.
+    pub const ANIMATION_CYCLE: f32 = Self::ANIMATION_FRAMES as f32 * Self::FRAME_DURATION;
+    const WAVE_VELOCITY_STEP: f32 = 0.15;
+    const WAVE_SPAWN_BEAM_STEP: f32 = 0.25;
 
     pub fn get_shape(sprite_sheet: &Rc<SpriteSheet>) -> Shape {
         let cell = sprite_sheet
@@ -72,13 +97,26 @@ impl Ferris {
         Self {
             id: Id::new(),
             position,
+            last_position: position,
+            subpixel: (0.0, 0.0),
             sprite_sheet,
             animation,
             color,
             state: FerrisState::MovingLeft,
+            resume_state: FerrisState::MovingLeft,
+            velocity: Self::DEFAULT_VELOCITY,
+            speed_scale: 1.0,
+            spawn_beam_ratio: Self::SPAWN_BEAM_RATIO,
         }
     }
 
+    pub fn with_wave(mut self, wave: u32) -> Self {
+        let level = wave.saturating_sub(1) as f32;
+        self.velocity = Self::DEFAULT_VELOCITY * (1.0 + level * Self::WAVE_VELOCITY_STEP);
+        self.spawn_beam_ratio = Self::SPAWN_BEAM_RATIO * (1.0 + level * Self::WAVE_SPAWN_BEAM_STEP);
+        self
+    }
+
     fn get_beam_spawn_point(&self) -> Point {
         let cell = self.get_current_frame_cell().expect("cell not found");
         let ferris_shape = cell.shape();
@@ -96,13 +134,10 @@ impl Ferris {
     }
 
     fn new_animation(color: FerrisColor) -> Sequence {
-        const FERRIS_ANIMATION_FRAMES: usize = 4;
-
-        let frames = (0..=FERRIS_ANIMATION_FRAMES).map(|i| {
+        let frames = (0..Self::ANIMATION_FRAMES).map(|i| {
             let color_str: &str = color.into();
             let cell_name = format!("ferris_{}_{}.png", color_str, i);
-            let duration = 150.0;
-            Frame::new(cell_name, duration)
+            Frame::new(cell_name, Self::FRAME_DURATION)
         });
         Sequence::new(frames.collect())
     }
@@ -119,24 +154,32 @@ impl Ferris {
         Rect::new(position, shape)
     }
 
-    pub fn update(&mut self, delta_ms: f32) -> Option<GameCommand> {
+    pub fn update(&mut self, delta_ms: f32, rng: &mut Rng) -> Option<GameCommand> {
+        self.last_position = self.position;
+        if let FerrisState::Idle = self.state {
+            return None;
+        }
+
         self.animation.update(delta_ms);
-        self.position.x += (self.get_velocity_x() * delta_ms).round() as i16;
-        self.position.y += (self.get_velocity_y() * delta_ms).round() as i16;
+        let (velocity_x, velocity_y) = (self.get_velocity_x(), self.get_velocity_y());
+        self.position.x += whole_pixels(&mut self.subpixel.0, velocity_x * delta_ms);
+        self.position.y += whole_pixels(&mut self.subpixel.1, velocity_y * delta_ms);
 
         match self.state {
             FerrisState::TurnLeft(ahead_position_y) if ahead_position_y < self.position.y => {
                 self.position.y = ahead_position_y;
+                self.subpixel.1 = 0.0;
                 self.move_left();
             }
             FerrisState::TurnRight(ahead_position_y) if ahead_position_y < self.position.y => {
                 self.position.y = ahead_position_y;
+                self.subpixel.1 = 0.0;
                 self.move_right();
             }
             _ => {}
         }
 
-        if rand::random::<f32>() < Self::SPAWN_BEAM_RATIO {
+        if rng.next_f32() < self.spawn_beam_ratio {
             let beam = Beam::new(
                 self.sprite_sheet.clone(),
                 self.get_beam_spawn_point(),
@@ -148,13 +191,13 @@ impl Ferris {
         None
     }
 
-    pub fn draw(&self) -> Option<DrawCommand> {
+    pub fn draw(&self, alpha: f32) -> Option<DrawCommand> {
         let cell = self
             .get_current_frame_cell()
             .expect("cell not found")
             .clone();
         let sprite_sheet = self.sprite_sheet.clone();
-        let position = self.position;
+        let position = self.last_position.lerp(&self.position, alpha);
 
         Some(DrawCommand(
             layers::ENEMY,
@@ -170,9 +213,11 @@ impl Ferris {
 
     pub fn on_collide(&self, other: &GameCharacter) -> Option<GameCommand> {
         match other {
-            GameCharacter::Missile(_) | GameCharacter::Ferris(_) => {
-                Some(GameCommand::DestroyCharacter(self.id().clone()))
-            }
+            GameCharacter::Missile(_) => Some(GameCommand::DestroyEnemy(
+                self.id().clone(),
+                self.color.points(),
+            )),
+            GameCharacter::Ferris(_) => Some(GameCommand::DestroyCharacter(self.id().clone())),
             GameCharacter::Wall(wall) => match (wall.wall_type(), self.state) {
                 (WallType::Left, FerrisState::MovingLeft) => Some(GameCommand::TurnFerris),
                 (WallType::Right, FerrisState::MovingRight) => Some(GameCommand::TurnFerris),
@@ -191,11 +236,22 @@ impl Ferris {
     }
 
     pub fn stop(&mut self) {
+        if let FerrisState::Idle = self.state {
+            return;
+        }
+        self.resume_state = self.state;
         self.state = FerrisState::Idle;
     }
 
     pub fn start(&mut self) {
-        self.state = FerrisState::MovingRight;
+        if let FerrisState::Idle = self.state {
+            self.state = self.resume_state;
+        }
+    }
+
+    pub fn set_speed_scale(&mut self, speed_scale: f32) {
+        self.speed_scale = speed_scale;
+        self.animation.set_speed(speed_scale);
     }
 
     pub fn turn(&mut self) {
@@ -209,16 +265,27 @@ impl Ferris {
 
     fn get_velocity_x(&self) -> f32 {
         match self.state {
-            FerrisState::MovingLeft => -Self::DEFAULT_VELOCITY,
-            FerrisState::MovingRight => Self::DEFAULT_VELOCITY,
+            FerrisState::MovingLeft => -self.velocity * self.speed_scale,
+            FerrisState::MovingRight => self.velocity * self.speed_scale,
             _ => 0.0,
         }
     }
 
     fn get_velocity_y(&self) -> f32 {
         match self.state {
-            FerrisState::TurnLeft(_) | FerrisState::TurnRight(_) => Self::DEFAULT_VELOCITY,
+            FerrisState::TurnLeft(_) | FerrisState::TurnRight(_) => {
+                self.velocity * self.speed_scale
+            }
             _ => 0.0,
         }
     }
 }
+
+// Adds `distance` to what is left over from earlier moves and returns the
+// whole pixels of it, keeping the rest.
+fn whole_pixels(remainder: &mut f32, distance: f32) -> i16 {
+    let total = *remainder + distance;
+    let pixels = total.round();
+    *remainder = total - pixels;
+    pixels as i16
+}
@@ -1,44 +1,136 @@
 use std::marker::PhantomData;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+
+use crate::engine::input::InputState;
+
+// What a state asks of the runner once it has been updated.
+pub enum Transition<S> {
+    // Carry on as this state, without calling any hooks.
+    Stay(S),
+    // Exit the top state and enter this one in its place.
+    Replace(S),
+    // Pause the top state and enter this one on top of it.
+    Push(S),
+    // Exit the top state and resume the one underneath.
+    Pop,
+    // Exit every state and enter this one on its own.
+    Reset(S),
+}
 
 pub trait State<E, SM: StateMachine<E>> {
-    fn update(&self, delta: f32, events: &[E]) -> SM;
+    fn update(&self, delta: f32, input: &InputState, events: &[E]) -> Transition<SM>;
     fn on_enter(&mut self) -> Result<()>;
     fn on_exit(&mut self) -> Result<()>;
+
+    // Called when another state is pushed on top of this one and when that
+    // state is popped again.
+    fn on_pause(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_resume(&mut self) -> Result<()> {
+        Ok(())
+    }
 }
 
-pub trait StateMachine<E> {
-    fn update(&self, delta: f32, events: &[E]) -> Self;
+pub trait StateMachine<E>: Sized {
+    fn update(&self, delta: f32, input: &InputState, events: &[E]) -> Transition<Self>;
     fn on_enter(&mut self) -> Result<()>;
     fn on_exit(&mut self) -> Result<()>;
+    fn on_pause(&mut self) -> Result<()>;
+    fn on_resume(&mut self) -> Result<()>;
 }
 
-pub struct StateMachineRunner<E, S: StateMachine<E> + Default + PartialEq> {
-    pub state: S,
+// Only the top of the stack is updated. The states underneath are frozen
+// until it is popped, but are still there to be drawn behind it.
+pub struct StateMachineRunner<E, S: StateMachine<E>> {
+    stack: Vec<S>,
     phantom: PhantomData<E>,
 }
 
-impl<E, S: StateMachine<E> + Default + PartialEq> StateMachineRunner<E, S> {
+impl<E, S: StateMachine<E> + Default> StateMachineRunner<E, S> {
     pub fn new() -> Self {
         Self {
-            state: S::default(),
+            stack: vec![S::default()],
             phantom: PhantomData,
         }
     }
+}
+
+impl<E, S: StateMachine<E> + Default> Default for StateMachineRunner<E, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E, S: StateMachine<E>> StateMachineRunner<E, S> {
+    pub fn state(&self) -> &S {
+        self.stack.last().expect("state stack is never empty")
+    }
+
+    // From the bottom of the stack to the top.
+    pub fn states(&self) -> impl DoubleEndedIterator<Item = &S> {
+        self.stack.iter()
+    }
 
-    pub fn update(&mut self, delta: f32, events: &[E]) -> Result<()> {
-        let next_state = self.state.update(delta, events);
-        self.transition(next_state)
+    pub fn update(&mut self, delta: f32, input: &InputState, events: &[E]) -> Result<()> {
+        let transition = self.state().update(delta, input, events);
+        self.apply(transition)
     }
 
-    pub fn transition(&mut self, mut next_state: S) -> Result<()> {
-        if next_state != self.state {
-            self.state.on_exit()?;
-            next_state.on_enter()?;
+    pub fn apply(&mut self, transition: Transition<S>) -> Result<()> {
+        match transition {
+            Transition::Stay(state) => {
+                if let Some(top) = self.stack.last_mut() {
+                    *top = state;
+                }
+                Ok(())
+            }
+            Transition::Replace(state) => self.replace(state),
+            Transition::Push(state) => self.push(state),
+            Transition::Pop => self.pop(),
+            Transition::Reset(state) => self.reset(state),
         }
-        self.state = next_state;
+    }
+
+    pub fn replace(&mut self, mut state: S) -> Result<()> {
+        if let Some(mut top) = self.stack.pop() {
+            top.on_exit()?;
+        }
+        state.on_enter()?;
+        self.stack.push(state);
+        Ok(())
+    }
+
+    pub fn push(&mut self, mut state: S) -> Result<()> {
+        if let Some(top) = self.stack.last_mut() {
+            top.on_pause()?;
+        }
+        state.on_enter()?;
+        self.stack.push(state);
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Result<()> {
+        if self.stack.len() < 2 {
+            return Err(anyhow!("Cannot pop the last state"));
+        }
+        if let Some(mut top) = self.stack.pop() {
+            top.on_exit()?;
+        }
+        if let Some(top) = self.stack.last_mut() {
+            top.on_resume()?;
+        }
+        Ok(())
+    }
 
+    pub fn reset(&mut self, mut state: S) -> Result<()> {
+        while let Some(mut top) = self.stack.pop() {
+            top.on_exit()?;
+        }
+        state.on_enter()?;
+        self.stack.push(state);
         Ok(())
     }
 }
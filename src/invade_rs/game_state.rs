@@ -1,40 +1,78 @@
 use anyhow::Result;
 
 use crate::engine::event::Event;
+use crate::engine::input::InputState;
 use crate::engine::DrawCommand;
 
-use super::fsm::{State, StateMachine};
+use super::fsm::{State, StateMachine, Transition};
+use super::score::Score;
 
 use self::created::*;
 use self::in_game::*;
 use self::out_game::*;
+use self::paused::*;
+use self::rebinding::*;
 
 pub mod created;
 pub mod in_game;
 pub mod out_game;
+pub mod paused;
+pub mod rebinding;
 
 pub enum GameStateMachine {
     Created(Created),
     OutGame(OutGame),
     InGame(InGame),
+    Paused(Paused),
+    Rebinding(Rebinding),
 }
 
 impl GameStateMachine {
-    pub fn draw(&self) -> Vec<DrawCommand> {
+    pub fn draw(&self, alpha: f32) -> Vec<DrawCommand> {
         match self {
-            Self::Created(state) => state.draw(),
-            Self::OutGame(state) => state.draw(),
-            Self::InGame(state) => state.draw(),
+            Self::Created(state) => state.draw(alpha),
+            Self::OutGame(state) => state.draw(alpha),
+            Self::InGame(state) => state.draw(alpha),
+            Self::Paused(state) => state.draw(alpha),
+            Self::Rebinding(state) => state.draw(alpha),
+        }
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        match self {
+            Self::Created(_) => None,
+            Self::OutGame(state) => Some(state.seed()),
+            Self::InGame(state) => Some(state.seed()),
+            Self::Paused(_) => None,
+            Self::Rebinding(_) => None,
+        }
+    }
+
+    pub fn lives(&self) -> Option<u32> {
+        match self {
+            Self::OutGame(state) => Some(state.lives()),
+            Self::InGame(state) => Some(state.lives()),
+            _ => None,
+        }
+    }
+
+    pub fn score(&self) -> Option<Score> {
+        match self {
+            Self::OutGame(state) => Some(state.score()),
+            Self::InGame(state) => Some(state.score()),
+            _ => None,
         }
     }
 }
 
 impl StateMachine<Event> for GameStateMachine {
-    fn update(&self, delta: f32, events: &[Event]) -> Self {
+    fn update(&self, delta: f32, input: &InputState, events: &[Event]) -> Transition<Self> {
         match self {
-            Self::Created(state) => state.update(delta, events),
-            Self::OutGame(state) => state.update(delta, events),
-            Self::InGame(state) => state.update(delta, events),
+            Self::Created(state) => state.update(delta, input, events),
+            Self::OutGame(state) => state.update(delta, input, events),
+            Self::InGame(state) => state.update(delta, input, events),
+            Self::Paused(state) => state.update(delta, input, events),
+            Self::Rebinding(state) => state.update(delta, input, events),
         }
     }
 
@@ -43,6 +81,8 @@ impl StateMachine<Event> for GameStateMachine {
             Self::Created(state) => state.on_enter(),
             Self::OutGame(state) => state.on_enter(),
             Self::InGame(state) => state.on_enter(),
+            Self::Paused(state) => state.on_enter(),
+            Self::Rebinding(state) => state.on_enter(),
         }
     }
 
@@ -51,23 +91,34 @@ impl StateMachine<Event> for GameStateMachine {
             Self::Created(state) => state.on_exit(),
             Self::OutGame(state) => state.on_exit(),
             Self::InGame(state) => state.on_exit(),
+            Self::Paused(state) => state.on_exit(),
+            Self::Rebinding(state) => state.on_exit(),
         }
     }
-}
 
-impl Default for GameStateMachine {
-    fn default() -> Self {
-        Self::Created(Created {})
+    fn on_pause(&mut self) -> Result<()> {
+        match self {
+            Self::Created(state) => state.on_pause(),
+            Self::OutGame(state) => state.on_pause(),
+            Self::InGame(state) => state.on_pause(),
+            Self::Paused(state) => state.on_pause(),
+            Self::Rebinding(state) => state.on_pause(),
+        }
+    }
+
+    fn on_resume(&mut self) -> Result<()> {
+        match self {
+            Self::Created(state) => state.on_resume(),
+            Self::OutGame(state) => state.on_resume(),
+            Self::InGame(state) => state.on_resume(),
+            Self::Paused(state) => state.on_resume(),
+            Self::Rebinding(state) => state.on_resume(),
+        }
     }
 }
 
-impl PartialEq for GameStateMachine {
-    fn eq(&self, other: &Self) -> bool {
-        matches!(
-            (self, other),
-            (Self::Created(_), Self::Created(_))
-                | (Self::OutGame(_), Self::OutGame(_))
-                | (Self::InGame(_), Self::InGame(_))
-        )
+impl Default for GameStateMachine {
+    fn default() -> Self {
+        Self::Created(Created::new())
     }
 }
@@ -1,9 +1,10 @@
 use anyhow::Result;
 
 use crate::engine::event::Event;
+use crate::engine::input::InputState;
 use crate::engine::DrawCommand;
 
-use super::super::fsm::State;
+use super::super::fsm::{State, Transition};
 use super::GameStateMachine;
 
 #[derive(Clone)]
@@ -14,14 +15,25 @@ impl Created {
         Self {}
     }
 
-    pub fn draw(&self) -> Vec<DrawCommand> {
+    pub fn draw(&self, _alpha: f32) -> Vec<DrawCommand> {
         vec![]
     }
 }
 
+impl Default for Created {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl State<Event, GameStateMachine> for Created {
-    fn update(&self, _delta_ms: f32, _events: &[Event]) -> GameStateMachine {
-        GameStateMachine::Created(self.clone())
+    fn update(
+        &self,
+        _delta_ms: f32,
+        _input: &InputState,
+        _events: &[Event],
+    ) -> Transition<GameStateMachine> {
+        Transition::Stay(GameStateMachine::Created(self.clone()))
     }
 
     fn on_enter(&mut self) -> Result<()> {
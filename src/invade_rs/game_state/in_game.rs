@@ -3,17 +3,27 @@ use std::rc::Rc;
 
 use anyhow::Result;
 
-
 use crate::engine::event::Event;
 use crate::engine::geometry::{Point, Rect};
+use crate::engine::input::InputState;
+use crate::engine::random::Rng;
 use crate::engine::sprite::SpriteSheet;
 use crate::engine::DrawCommand;
 
-use super::super::character::{GameCharacter, GameCommand};
-use super::super::fsm::State;
+use super::super::character::{GameCharacter, GameCommand, Id};
+use super::super::config::Config;
+use super::super::controls::{Action, ControlScheme, Controls};
+use super::super::explosion::Explosion;
+use super::super::fsm::{State, Transition};
+use super::super::hud::Hud;
+use super::super::mask_shield::Crater;
+use super::super::progress::Progress;
+use super::super::score::Score;
 use super::super::ship::Ship;
+use super::super::sound::{SoundBank, SoundEffect};
 use super::super::turbo_fish;
 use super::out_game::OutGame;
+use super::paused::Paused;
 use super::GameStateMachine;
 
 const SCREEN_RECT: Rect = Rect::new_from_x_y_w_h(0, 0, 600, 600);
@@ -23,36 +33,77 @@ pub struct InGame {
     sprite_sheet: Rc<SpriteSheet>,
     pub characters: Vec<Rc<RefCell<GameCharacter>>>,
     pub player: Rc<RefCell<Ship>>,
+    sounds: Rc<SoundBank>,
+    controls: Rc<Controls>,
+    config: Config,
+    rng: Rc<RefCell<Rng>>,
+    progress: Progress,
+    fleet_size: usize,
+    hud: Hud,
     is_game_over: bool,
 }
 
 impl InGame {
-    fn apply_event(&self, event: &Event) {
-        match event {
-            Event::KeyDown(key) => match key.as_str() {
-                "ArrowRight" => self.player.borrow_mut().move_right(),
-                "ArrowLeft" => self.player.borrow_mut().move_left(),
-                "Space" => self.player.borrow_mut().shot(),
-                _ => {}
-            },
-            Event::KeyUp(key) => match key.as_str() {
-                "ArrowRight" => self.player.borrow_mut().stop(),
-                "ArrowLeft" => self.player.borrow_mut().stop(),
-                "Space" => self.player.borrow_mut().reload(),
-                _ => {}
-            },
+    // Movement and reloading follow what is held rather than individual key
+    // events, so overlapping presses and releases cannot leave the ship stuck.
+    // With both directions held the one pressed last wins; a held touch
+    // button counts as pressed before any key.
+    fn apply_input(&self, input: &InputState) {
+        let bindings = self.controls.bindings();
+        let touch = self.controls.touch();
+        let mouse = self.controls.mouse();
+        let use_mouse = self.controls.scheme() == ControlScheme::Mouse;
+        let is_held = |action| {
+            input.is_action_held(&bindings, action)
+                || touch.is_held(action)
+                || (use_mouse && action == Action::Fire && mouse.is_held())
+        };
+        let left_is_newer = input.action_held_since(&bindings, Action::MoveLeft)
+            > input.action_held_since(&bindings, Action::MoveRight);
+        let mut player = self.player.borrow_mut();
+
+        match (
+            is_held(Action::MoveLeft),
+            is_held(Action::MoveRight),
+            mouse.x(),
+        ) {
+            (true, false, _) => player.move_left(),
+            (false, true, _) => player.move_right(),
+            (true, true, _) if left_is_newer => player.move_left(),
+            (true, true, _) => player.move_right(),
+            (false, false, Some(x)) if use_mouse => player.seek(x),
+            _ => player.stop(),
+        }
+
+        if input.was_action_pressed(&bindings, Action::Fire)
+            || touch.was_pressed(Action::Fire)
+            || (use_mouse && mouse.was_pressed())
+        {
+            player.shot();
+        }
+        if !is_held(Action::Fire) {
+            player.reload();
         }
     }
 
+    fn pause(&self) -> Transition<GameStateMachine> {
+        Transition::Push(GameStateMachine::Paused(Paused::new(
+            self.new_game(),
+            self.sprite_sheet.clone(),
+            self.controls.clone(),
+        )))
+    }
+
     fn update_game(&self, delta: f32) -> Vec<GameCommand> {
         const TURBO_FISH_APPEAR_PROBABILITY: f32 = 0.001;
 
+        let mut rng = self.rng.borrow_mut();
         let mut commands = vec![];
         for c in self.characters.iter() {
             let mut c = c.borrow_mut();
 
             let cur_visible = c.bounding_box().intersects(&SCREEN_RECT);
-            if let Some(command) = c.update(delta) {
+            if let Some(command) = c.update(delta, &mut rng) {
                 commands.push(command);
             }
             let next_visible = c.bounding_box().intersects(&SCREEN_RECT);
@@ -72,7 +123,7 @@ impl InGame {
                     continue;
                 }
 
-                if c.bounding_box().intersects(&other.bounding_box()) {
+                if c.intersects(&other) {
                     if let Some(command) = c.on_collide(&other) {
                         commands.push(command);
                     }
@@ -80,7 +131,7 @@ impl InGame {
             }
 
             let player = self.player.borrow();
-            if c.bounding_box().intersects(&player.bounding_box()) {
+            if player.is_alive() && c.bounding_box().intersects(&player.bounding_box()) {
                 if let Some(command) = c.on_collide(&player.clone().into()) {
                     commands.push(command);
                 }
@@ -90,12 +141,24 @@ impl InGame {
             }
         }
 
-        if let Some(command) = self.player.borrow_mut().update(delta) {
+        if self.progress.lives > 0 && self.has_fleet_landed() {
+            commands.push(GameCommand::LandFleet);
+        }
+
+        if let Some(command) = self.player.borrow_mut().update(delta, &mut rng) {
             commands.push(command);
         }
 
-        if rand::random::<f32>() < TURBO_FISH_APPEAR_PROBABILITY {
-            commands.push(self.create_spawn_turbo_fish_command(&self.sprite_sheet));
+        let speed = self
+            .config
+            .fleet_speed_curve
+            .multiplier(self.enemy_count(), self.fleet_size);
+        commands.push(GameCommand::SetFleetSpeed(speed));
+        let fleet_moving = self.player.borrow().is_alive();
+        self.sounds.update_march(delta, speed, fleet_moving);
+
+        if rng.next_f32() < TURBO_FISH_APPEAR_PROBABILITY {
+            commands.push(self.create_spawn_turbo_fish_command(&self.sprite_sheet, &mut rng));
         }
         commands
     }
@@ -103,11 +166,60 @@ impl InGame {
     fn apply_command(&mut self, command: GameCommand) {
         match command {
             GameCommand::SpawnCharacter(new_character) => {
+                match &new_character {
+                    GameCharacter::Missile(_) => self.sounds.play(SoundEffect::Shoot),
+                    GameCharacter::Beam(_) => self.sounds.play(SoundEffect::EnemyShoot),
+                    GameCharacter::TurboFish(_) => {
+                        self.sounds.set_looping(SoundEffect::UfoFlyby, true)
+                    }
+                    _ => {}
+                }
                 self.characters.push(Rc::new(RefCell::new(new_character)));
             }
             GameCommand::DestroyCharacter(id) => {
                 self.characters.retain(|c| c.borrow().id() != &id);
             }
+            // Two collisions in one frame can destroy the same enemy, so it
+            // only scores once it has actually been removed.
+            GameCommand::DestroyEnemy(id, points) => {
+                if self.explode_character(&id, SoundEffect::EnemyDeath) {
+                    self.progress.score.add(points);
+                }
+            }
+            GameCommand::ExplodeCharacter(id) => {
+                self.explode_character(&id, SoundEffect::Impact);
+            }
+            GameCommand::PlaySound(effect) => {
+                self.sounds.play(effect);
+            }
+            GameCommand::DamageShield(id, damage) => {
+                self.sounds.play(SoundEffect::ShieldHit);
+                self.characters.retain(|c| {
+                    let mut c = c.borrow_mut();
+                    match &mut *c {
+                        GameCharacter::ShieldElement(shield) if shield.id() == &id => {
+                            shield.damage(damage);
+                            !shield.is_worn_out()
+                        }
+                        _ => true,
+                    }
+                });
+            }
+            GameCommand::ErodeShield(id, crater) => {
+                if let Crater::Blast(_) = crater {
+                    self.sounds.play(SoundEffect::ShieldHit);
+                }
+                self.characters.retain(|c| {
+                    let mut c = c.borrow_mut();
+                    match &mut *c {
+                        GameCharacter::MaskShield(shield) if shield.id() == &id => {
+                            shield.erode(&crater);
+                            !shield.is_worn_out()
+                        }
+                        _ => true,
+                    }
+                });
+            }
             GameCommand::TurnFerris => {
                 for c in self.characters.iter() {
                     let mut c = c.borrow_mut();
@@ -116,33 +228,207 @@ impl InGame {
                     }
                 }
             }
+            GameCommand::SetFleetSpeed(speed) => {
+                for c in self.characters.iter() {
+                    let mut c = c.borrow_mut();
+                    if let GameCharacter::Ferris(ferris) = &mut *c {
+                        ferris.set_speed_scale(speed);
+                    }
+                }
+            }
             GameCommand::DestroyPlayer => {
-                self.is_game_over = true;
+                let mut player = self.player.borrow_mut();
+                if player.is_vulnerable() {
+                    self.sounds.play(SoundEffect::PlayerDeath);
+                    player.explode();
+                    self.progress.lives = self.progress.lives.saturating_sub(1);
+                    self.freeze_fleet(true);
+                }
+            }
+            // There is no dodging a fleet that is down on the ship's row, so
+            // it ends the game instead of taking the lives one at a time.
+            GameCommand::LandFleet => {
+                let mut player = self.player.borrow_mut();
+                if player.is_alive() {
+                    self.sounds.play(SoundEffect::PlayerDeath);
+                    player.explode();
+                }
+                self.progress.lives = 0;
+                self.freeze_fleet(true);
+            }
+            GameCommand::RespawnPlayer => {
+                if self.progress.lives == 0 {
+                    self.is_game_over = true;
+                } else {
+                    self.sounds.play(SoundEffect::Respawn);
+                    self.player.borrow_mut().respawn();
+                    self.freeze_fleet(false);
+                }
+            }
+        }
+    }
+
+    // Returns whether the character was still there to explode.
+    fn explode_character(&mut self, id: &Id, sound: SoundEffect) -> bool {
+        let center = self
+            .characters
+            .iter()
+            .find(|c| c.borrow().id() == id)
+            .map(|c| c.borrow().bounding_box().center());
+        let Some(center) = center else {
+            return false;
+        };
+
+        self.characters.retain(|c| c.borrow().id() != id);
+        // Projectiles that left the screen burst at its edge.
+        let center = SCREEN_RECT.clamp(&center);
+        let explosion = Explosion::new(self.sprite_sheet.clone(), center, sound);
+        self.characters
+            .push(Rc::new(RefCell::new(explosion.into())));
+        true
+    }
+
+    fn update_flyby(&self) {
+        let has_turbo_fish = self
+            .characters
+            .iter()
+            .any(|c| matches!(&*c.borrow(), GameCharacter::TurboFish(_)));
+        self.sounds
+            .set_looping(SoundEffect::UfoFlyby, has_turbo_fish);
+    }
+
+    fn enemy_count(&self) -> usize {
+        count_ferris(&self.characters)
+    }
+
+    fn has_fleet_landed(&self) -> bool {
+        let row = self.player.borrow().bounding_box().top();
+        self.characters.iter().any(|c| {
+            let c = c.borrow();
+            matches!(&*c, GameCharacter::Ferris(_)) && row <= c.bounding_box().bottom()
+        })
+    }
+
+    fn freeze_fleet(&self, frozen: bool) {
+        for c in self.characters.iter() {
+            let mut c = c.borrow_mut();
+            if let GameCharacter::Ferris(ferris) = &mut *c {
+                if frozen {
+                    ferris.stop();
+                } else {
+                    ferris.start();
+                }
             }
-            _ => {}
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         sprite_sheet: Rc<SpriteSheet>,
         characters: Vec<Rc<RefCell<GameCharacter>>>,
         player: Rc<RefCell<Ship>>,
+        sounds: Rc<SoundBank>,
+        controls: Rc<Controls>,
+        config: Config,
+        rng: Rng,
+        progress: Progress,
     ) -> Self {
+        let hud = Hud::new(sprite_sheet.clone(), SCREEN_RECT.width());
+        let fleet_size = count_ferris(&characters);
+
         Self {
             sprite_sheet,
             characters,
             player,
+            sounds,
+            controls,
+            config,
+            rng: Rc::new(RefCell::new(rng)),
+            progress,
+            fleet_size,
+            hud,
             is_game_over: false,
         }
     }
-    pub fn draw(&self) -> Vec<DrawCommand> {
+
+    pub fn seed(&self) -> u64 {
+        self.rng.borrow().seed()
+    }
+
+    pub fn lives(&self) -> u32 {
+        self.progress.lives
+    }
+
+    pub fn score(&self) -> Score {
+        self.progress.score
+    }
+
+    fn new_game(&self) -> NewGame {
+        NewGame {
+            sprite_sheet: self.sprite_sheet.clone(),
+            sounds: self.sounds.clone(),
+            controls: self.controls.clone(),
+            config: self.config,
+            rng: self.rng.clone(),
+            high_score: self.progress.score.high(),
+        }
+    }
+
+    // A lost game goes back to the title, like quitting it does.
+    fn next_game(&self) -> GameStateMachine {
+        self.new_game().quit()
+    }
+
+    fn next_wave(&self) -> GameStateMachine {
+        let seed = self.rng.borrow_mut().next_u64();
+        let progress = self.progress.next_wave();
+        let out_game = OutGame::new(
+            self.sprite_sheet.clone(),
+            self.sounds.clone(),
+            self.controls.clone(),
+            self.config,
+            seed,
+            progress,
+        );
+
+        let interval = self.config.shield_restore_interval;
+        if (progress.wave - 1).is_multiple_of(interval) {
+            return GameStateMachine::OutGame(out_game);
+        }
+
+        let shields = self
+            .characters
+            .iter()
+            .filter(|c| c.borrow().is_shield())
+            .cloned()
+            .collect();
+        GameStateMachine::OutGame(out_game.with_shields(shields))
+    }
+
+    pub fn draw(&self, alpha: f32) -> Vec<DrawCommand> {
         let mut draw_commands = vec![];
-        draw_commands.append(&mut self.characters.iter().map(|c| c.borrow().draw()).collect());
-        draw_commands.push(self.player.borrow().draw());
-        draw_commands.into_iter().flatten().collect()
+        draw_commands.append(
+            &mut self
+                .characters
+                .iter()
+                .map(|c| c.borrow().draw(alpha))
+                .collect(),
+        );
+        draw_commands.push(self.player.borrow().draw(alpha));
+        let mut draw_commands: Vec<DrawCommand> = draw_commands.into_iter().flatten().collect();
+        draw_commands.append(&mut self.hud.draw(&self.progress.score, self.progress.lives));
+        let touch = self.controls.touch();
+        if touch.is_enabled() {
+            draw_commands.append(&mut self.hud.draw_touch_controls(&touch));
+        }
+        draw_commands
     }
 
-    fn create_spawn_turbo_fish_command(&self, sprite_sheet: &Rc<SpriteSheet>) -> GameCommand {
+    fn create_spawn_turbo_fish_command(
+        &self,
+        sprite_sheet: &Rc<SpriteSheet>,
+        rng: &mut Rng,
+    ) -> GameCommand {
         const Y_ORIGIN: i16 = 50;
 
         let ship_shape = turbo_fish::TurboFish::get_shape(sprite_sheet);
@@ -152,14 +438,67 @@ impl InGame {
             x: x_origin,
             y: Y_ORIGIN,
         };
-        let turbo_fish = turbo_fish::TurboFish::new(sprite_sheet.clone(), position);
+        let turbo_fish = turbo_fish::TurboFish::new(sprite_sheet.clone(), position, rng);
         GameCommand::SpawnCharacter(turbo_fish.into())
     }
 }
 
+// What a fresh game is started from, so the pause menu can restart or quit
+// without holding on to the game itself.
+#[derive(Clone)]
+pub struct NewGame {
+    sprite_sheet: Rc<SpriteSheet>,
+    sounds: Rc<SoundBank>,
+    controls: Rc<Controls>,
+    config: Config,
+    rng: Rc<RefCell<Rng>>,
+    high_score: u32,
+}
+
+impl NewGame {
+    fn start(&self) -> OutGame {
+        let seed = self.rng.borrow_mut().next_u64();
+        let progress = Progress::new(&self.config, Score::new(self.high_score));
+        OutGame::new(
+            self.sprite_sheet.clone(),
+            self.sounds.clone(),
+            self.controls.clone(),
+            self.config,
+            seed,
+            progress,
+        )
+    }
+
+    pub fn restart(&self) -> GameStateMachine {
+        GameStateMachine::OutGame(self.start())
+    }
+
+    pub fn quit(&self) -> GameStateMachine {
+        GameStateMachine::OutGame(self.start().with_start_prompt())
+    }
+}
+
+fn count_ferris(characters: &[Rc<RefCell<GameCharacter>>]) -> usize {
+    characters
+        .iter()
+        .filter(|c| matches!(&*c.borrow(), GameCharacter::Ferris(_)))
+        .count()
+}
+
 impl State<Event, GameStateMachine> for InGame {
-    fn update(&self, delta: f32, events: &[Event]) -> GameStateMachine {
-        events.iter().for_each(|event| self.apply_event(event));
+    fn update(
+        &self,
+        delta: f32,
+        input: &InputState,
+        events: &[Event],
+    ) -> Transition<GameStateMachine> {
+        // Nobody is watching once the page loses focus, so stop the game.
+        if input.was_action_pressed(&self.controls.bindings(), Action::Pause)
+            || events.iter().any(Event::is_focus_loss)
+        {
+            return self.pause();
+        }
+        self.apply_input(input);
 
         let commands = self.update_game(delta);
 
@@ -168,33 +507,38 @@ impl State<Event, GameStateMachine> for InGame {
             for c in commands.into_iter() {
                 next_state.apply_command(c);
             }
+            next_state.update_flyby();
             next_state
         };
 
-        let mut enemy_count = 0;
-        for c in self.characters.iter() {
-            let c = c.borrow();
-            if let GameCharacter::Ferris(_) = &*c {
-                enemy_count += 1;
-            }
+        // The next state is built from this frame's outcome, so a last hit on
+        // a shield or the last points scored carry over.
+        if next_state.enemy_count() == 0 {
+            return Transition::Replace(next_state.next_wave());
         }
 
-        if enemy_count == 0 {
-            return GameStateMachine::OutGame(OutGame::new(self.sprite_sheet.clone()));
+        if next_state.is_game_over {
+            return Transition::Replace(next_state.next_game());
         }
 
-        if self.is_game_over {
-            return GameStateMachine::OutGame(OutGame::new(self.sprite_sheet.clone()));
-        }
-
-        GameStateMachine::InGame(next_state)
+        Transition::Stay(GameStateMachine::InGame(next_state))
     }
 
     fn on_enter(&mut self) -> Result<()> {
+        self.sounds.restart_march();
         Ok(())
     }
 
     fn on_exit(&mut self) -> Result<()> {
+        self.sounds.stop_all();
+        Ok(())
+    }
+
+    // Only the looping sounds such as the flyby are stopped, and the next
+    // update starts them again. The march is simply not stepped while paused,
+    // so it carries on from where it was.
+    fn on_pause(&mut self) -> Result<()> {
+        self.sounds.stop_all();
         Ok(())
     }
 }
@@ -5,38 +5,69 @@ use anyhow::Result;
 
 use crate::engine::event::Event;
 use crate::engine::geometry::{Point, Rect};
+use crate::engine::input::InputState;
+use crate::engine::random::Rng;
 use crate::engine::sprite::SpriteSheet;
 use crate::engine::DrawCommand;
 
 use super::super::character::GameCharacter;
+use super::super::config::{Config, ShieldStyle};
+use super::super::controls::{Action, Controls};
 use super::super::ferris::{Ferris, FerrisColor};
-use super::super::fsm::State;
+use super::super::fsm::{State, Transition};
+use super::super::hud::Hud;
+use super::super::mask_shield::MaskShield;
+use super::super::progress::Progress;
+use super::super::score::Score;
 use super::super::shield::{create_shield, ShieldElement};
 use super::super::ship::Ship;
+use super::super::sound::SoundBank;
 use super::super::wall::{Wall, WallType};
 use super::in_game::InGame;
+use super::rebinding::Rebinding;
 use super::GameStateMachine;
 
 const SCREEN_RECT: Rect = Rect::new_from_x_y_w_h(0, 0, 600, 600);
+const HINT_OFFSET_Y: i16 = 48;
+const KEY_CONTROLS: [&str; 2] = ["KeyC", "PadSelect"];
 
 #[derive(Clone)]
 pub struct OutGame {
     sprite_sheet: Rc<SpriteSheet>,
+    sounds: Rc<SoundBank>,
+    controls: Rc<Controls>,
     characters: Vec<Rc<RefCell<GameCharacter>>>,
     player: Rc<RefCell<Ship>>,
+    config: Config,
+    seed: u64,
+    progress: Progress,
+    hud: Hud,
+    banner_remaining: f32,
+    waiting_for_start: bool,
 }
 
 impl OutGame {
-    pub fn new(sprite_sheet: Rc<SpriteSheet>) -> Self {
+    const BANNER_DURATION: f32 = 2000.0;
+
+    pub fn new(
+        sprite_sheet: Rc<SpriteSheet>,
+        sounds: Rc<SoundBank>,
+        controls: Rc<Controls>,
+        config: Config,
+        seed: u64,
+        progress: Progress,
+    ) -> Self {
         let characters = {
             let mut characters = vec![];
             characters.append(&mut Self::spawn_ferris_fleet(
                 &sprite_sheet,
                 SCREEN_RECT.width(),
+                progress.wave,
             ));
             characters.append(&mut Self::spawn_aligned_shields(
                 &sprite_sheet,
                 SCREEN_RECT.width(),
+                config.shield_style,
             ));
             characters.append(&mut Self::spawn_walls(
                 SCREEN_RECT.width(),
@@ -45,42 +76,116 @@ impl OutGame {
             characters
         };
         let player = Self::spawn_ship(&sprite_sheet, SCREEN_RECT.width());
+        let hud = Hud::new(sprite_sheet.clone(), SCREEN_RECT.width());
+        let banner_remaining = if progress.wave > 1 {
+            Self::BANNER_DURATION
+        } else {
+            0.0
+        };
 
         Self {
             sprite_sheet,
+            sounds,
+            controls,
             characters,
             player,
+            config,
+            seed,
+            progress,
+            hud,
+            banner_remaining,
+            waiting_for_start: false,
         }
     }
 
-    pub fn draw(&self) -> Vec<DrawCommand> {
+    // Holds the game on the title until the player fires or taps.
+    pub fn with_start_prompt(mut self) -> Self {
+        self.waiting_for_start = true;
+        self
+    }
+
+    fn controls_requested(&self, input: &InputState) -> bool {
+        KEY_CONTROLS.iter().any(|code| input.was_pressed(code))
+    }
+
+    fn start_requested(&self, input: &InputState, events: &[Event]) -> bool {
+        input.was_action_pressed(&self.controls.bindings(), Action::Fire)
+            || events
+                .iter()
+                .any(|event| matches!(event, Event::PointerDown(_)))
+    }
+
+    pub fn with_shields(mut self, shields: Vec<Rc<RefCell<GameCharacter>>>) -> Self {
+        self.characters.retain(|c| !c.borrow().is_shield());
+        self.characters.extend(shields);
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn lives(&self) -> u32 {
+        self.progress.lives
+    }
+
+    pub fn score(&self) -> Score {
+        self.progress.score
+    }
+
+    pub fn draw(&self, alpha: f32) -> Vec<DrawCommand> {
         let mut draw_commands = vec![];
-        draw_commands.append(&mut self.characters.iter().map(|c| c.borrow().draw()).collect());
-        draw_commands.push(self.player.borrow().draw());
-        draw_commands.into_iter().flatten().collect()
+        draw_commands.append(
+            &mut self
+                .characters
+                .iter()
+                .map(|c| c.borrow().draw(alpha))
+                .collect(),
+        );
+        draw_commands.push(self.player.borrow().draw(alpha));
+        let mut draw_commands: Vec<DrawCommand> = draw_commands.into_iter().flatten().collect();
+        draw_commands.append(&mut self.hud.draw(&self.progress.score, self.progress.lives));
+        if self.waiting_for_start {
+            draw_commands.push(
+                self.hud
+                    .draw_banner("PRESS FIRE", SCREEN_RECT.height() * 2 / 3),
+            );
+            draw_commands.push(
+                self.hud
+                    .draw_hint("C CONTROLS", SCREEN_RECT.height() * 2 / 3 + HINT_OFFSET_Y),
+            );
+        } else if 0.0 < self.banner_remaining {
+            let banner = format!("WAVE {}", self.progress.wave);
+            draw_commands.push(self.hud.draw_banner(&banner, SCREEN_RECT.height() * 2 / 3));
+        }
+        draw_commands
     }
 
     fn spawn_ferris_fleet(
         sprite_sheet: &Rc<SpriteSheet>,
         screen_width: i16,
+        wave: u32,
     ) -> Vec<Rc<RefCell<GameCharacter>>> {
         const FLEET_COLS: i16 = 9;
         const FLEET_ROWS: i16 = 2 * 3;
         const Y_ORIGIN: i16 = 100;
         const MARGIN: i16 = 10;
+        const WAVE_DROP: i16 = 16;
+        const MAX_WAVE_DROPS: u32 = 5;
 
         let ferris_shape = Ferris::get_shape(sprite_sheet);
         let x_origin = (screen_width - FLEET_COLS * (ferris_shape.width + MARGIN)) / 2;
-        let colors = vec![FerrisColor::Magenta, FerrisColor::Green, FerrisColor::Blue];
+        let y_origin = Y_ORIGIN + WAVE_DROP * (wave - 1).min(MAX_WAVE_DROPS) as i16;
+        let colors = [FerrisColor::Magenta, FerrisColor::Green, FerrisColor::Blue];
 
         let mut characters = vec![];
         for row in 0..FLEET_ROWS {
             let color = colors[(row / 2) as usize];
-            let y = Y_ORIGIN + row * (ferris_shape.height + MARGIN);
+            let y = y_origin + row * (ferris_shape.height + MARGIN);
             for col in 0..FLEET_COLS {
                 let x = x_origin + (MARGIN / 2) + col * (ferris_shape.width + MARGIN);
                 let position = Point { x, y };
-                let ferris = Ferris::new(sprite_sheet.clone(), position, color);
+                let ferris = Ferris::new(sprite_sheet.clone(), position, color).with_wave(wave);
                 characters.push(Rc::new(RefCell::new(ferris.into())));
             }
         }
@@ -91,21 +196,29 @@ impl OutGame {
     fn spawn_aligned_shields(
         sprite_sheet: &Rc<SpriteSheet>,
         screen_width: i16,
+        shield_style: ShieldStyle,
     ) -> Vec<Rc<RefCell<GameCharacter>>> {
         const SHIELD_NUM: i16 = 4;
         const Y_ORIGIN: i16 = 490;
 
-        let shield_element_shape = ShieldElement::get_shape(sprite_sheet);
-        let shield_width = 4 * shield_element_shape.width;
+        let shield_width = match shield_style {
+            ShieldStyle::Tiles => 4 * ShieldElement::get_shape(sprite_sheet).width,
+            ShieldStyle::Mask => MaskShield::get_shape().width,
+        };
         let margin = (screen_width - SHIELD_NUM * shield_width) / (2 * SHIELD_NUM);
 
         let mut characters = vec![];
         for i in 0..SHIELD_NUM {
             let x = margin + i * (shield_width + 2 * margin);
             let position = Point { x, y: Y_ORIGIN };
-            create_shield(sprite_sheet.clone(), &position)
-                .into_iter()
-                .for_each(|c| characters.push(Rc::new(RefCell::new(c.into()))));
+            match shield_style {
+                ShieldStyle::Tiles => create_shield(sprite_sheet.clone(), &position)
+                    .into_iter()
+                    .for_each(|c| characters.push(Rc::new(RefCell::new(c.into())))),
+                ShieldStyle::Mask => {
+                    characters.push(Rc::new(RefCell::new(MaskShield::new(position).into())))
+                }
+            }
         }
 
         characters
@@ -143,12 +256,40 @@ impl OutGame {
 }
 
 impl State<Event, GameStateMachine> for OutGame {
-    fn update(&self, _delta_ms: f32, _events: &[Event]) -> GameStateMachine {
-        GameStateMachine::InGame(InGame::new(
+    fn update(
+        &self,
+        delta_ms: f32,
+        input: &InputState,
+        events: &[Event],
+    ) -> Transition<GameStateMachine> {
+        if self.waiting_for_start {
+            if self.controls_requested(input) {
+                return Transition::Push(GameStateMachine::Rebinding(Rebinding::new(
+                    self.sprite_sheet.clone(),
+                    self.controls.clone(),
+                )));
+            }
+            let mut next_state = self.clone();
+            next_state.waiting_for_start = !self.start_requested(input, events);
+            return Transition::Stay(GameStateMachine::OutGame(next_state));
+        }
+
+        if 0.0 < self.banner_remaining {
+            let mut next_state = self.clone();
+            next_state.banner_remaining -= delta_ms;
+            return Transition::Stay(GameStateMachine::OutGame(next_state));
+        }
+
+        Transition::Replace(GameStateMachine::InGame(InGame::new(
             self.sprite_sheet.clone(),
             self.characters.clone(),
             self.player.clone(),
-        ))
+            self.sounds.clone(),
+            self.controls.clone(),
+            self.config,
+            Rng::new(self.seed),
+            self.progress,
+        )))
     }
 
     fn on_enter(&mut self) -> Result<()> {
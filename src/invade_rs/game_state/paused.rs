@@ -0,0 +1,212 @@
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use crate::engine::event::{Event, PointerButton};
+use crate::engine::font::{Align, BitmapFont};
+use crate::engine::geometry::{Point, Rect};
+use crate::engine::input::InputState;
+use crate::engine::sprite::SpriteSheet;
+use crate::engine::DrawCommand;
+
+use super::super::character::layers;
+use super::super::controls::{Action, Controls};
+use super::super::fsm::{State, Transition};
+use super::in_game::NewGame;
+use super::rebinding::Rebinding;
+use super::GameStateMachine;
+
+const SCREEN_RECT: Rect = Rect::new_from_x_y_w_h(0, 0, 600, 600);
+const DIM_COLOR: [u8; 4] = [0, 0, 0, 0xb0];
+const TITLE_Y: i16 = 200;
+const ITEM_Y: i16 = 280;
+const ITEM_SPACING: i16 = 40;
+const ITEM_WIDTH: i16 = 240;
+const CURSOR_X: i16 = 190;
+
+const KEY_UP: [&str; 2] = ["ArrowUp", "PadDPadUp"];
+const KEY_DOWN: [&str; 2] = ["ArrowDown", "PadDPadDown"];
+const KEY_SELECT: [&str; 2] = ["Enter", "PadSouth"];
+const KEY_RESUME: [&str; 2] = ["Escape", "PadEast"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MenuItem {
+    Resume,
+    Controls,
+    Restart,
+    Quit,
+}
+
+impl MenuItem {
+    const ALL: [MenuItem; 4] = [
+        MenuItem::Resume,
+        MenuItem::Controls,
+        MenuItem::Restart,
+        MenuItem::Quit,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            MenuItem::Resume => "RESUME",
+            MenuItem::Controls => "CONTROLS",
+            MenuItem::Restart => "RESTART",
+            MenuItem::Quit => "QUIT TO TITLE",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Paused {
+    new_game: NewGame,
+    sprite_sheet: Rc<SpriteSheet>,
+    controls: Rc<Controls>,
+    font: BitmapFont,
+    title_font: BitmapFont,
+    selected: usize,
+}
+
+impl Paused {
+    pub fn new(new_game: NewGame, sprite_sheet: Rc<SpriteSheet>, controls: Rc<Controls>) -> Self {
+        Self {
+            new_game,
+            font: BitmapFont::new(sprite_sheet.clone()),
+            title_font: BitmapFont::new(sprite_sheet.clone()).with_scale(2),
+            sprite_sheet,
+            controls,
+            selected: 0,
+        }
+    }
+
+    fn item_rect(i: usize) -> Rect {
+        Rect::new_from_x_y_w_h(
+            (SCREEN_RECT.width() - ITEM_WIDTH) / 2,
+            ITEM_Y + i as i16 * ITEM_SPACING - ITEM_SPACING / 2,
+            ITEM_WIDTH,
+            ITEM_SPACING,
+        )
+    }
+
+    fn select(&self, item: MenuItem) -> Transition<GameStateMachine> {
+        match item {
+            MenuItem::Resume => Transition::Pop,
+            MenuItem::Controls => Transition::Push(GameStateMachine::Rebinding(Rebinding::new(
+                self.sprite_sheet.clone(),
+                self.controls.clone(),
+            ))),
+            MenuItem::Restart => Transition::Reset(self.new_game.restart()),
+            MenuItem::Quit => Transition::Reset(self.new_game.quit()),
+        }
+    }
+
+    // The game itself is drawn underneath by the runner.
+    pub fn draw(&self, _alpha: f32) -> Vec<DrawCommand> {
+        let mut draw_commands = vec![DrawCommand(
+            layers::HUD,
+            Box::new(|renderer| renderer.fill_rect(&SCREEN_RECT, DIM_COLOR)),
+        )];
+        draw_commands.push(self.title_font.draw(
+            "PAUSED",
+            &Point {
+                x: SCREEN_RECT.width() / 2,
+                y: TITLE_Y,
+            },
+            Align::Center,
+            layers::HUD,
+        ));
+        for (i, item) in MenuItem::ALL.iter().enumerate() {
+            draw_commands.push(self.font.draw(
+                item.label(),
+                &Point {
+                    x: SCREEN_RECT.width() / 2,
+                    y: ITEM_Y + i as i16 * ITEM_SPACING,
+                },
+                Align::Center,
+                layers::HUD,
+            ));
+        }
+        draw_commands.push(self.draw_cursor());
+        draw_commands
+    }
+
+    fn draw_cursor(&self) -> DrawCommand {
+        const CURSOR_OFFSET_Y: i16 = 8;
+
+        let cell = self
+            .sprite_sheet
+            .cell("rust_logo_white.png")
+            .expect("cell not found")
+            .clone();
+        let sprite_sheet = self.sprite_sheet.clone();
+        let position = Point {
+            x: CURSOR_X,
+            y: ITEM_Y + self.selected as i16 * ITEM_SPACING - CURSOR_OFFSET_Y,
+        };
+        DrawCommand(
+            layers::HUD,
+            Box::new(move |renderer| {
+                sprite_sheet.draw(renderer, &cell, &position);
+            }),
+        )
+    }
+}
+
+impl State<Event, GameStateMachine> for Paused {
+    fn update(
+        &self,
+        _delta_ms: f32,
+        input: &InputState,
+        events: &[Event],
+    ) -> Transition<GameStateMachine> {
+        let rows = MenuItem::ALL.len();
+        let mut next_state = self.clone();
+        for event in events {
+            if let Event::PointerDown(pointer) = event {
+                if pointer.button != Some(PointerButton::Primary) {
+                    continue;
+                }
+                let item = (0..rows).find(|&i| Self::item_rect(i).contains(&pointer.position));
+                if let Some(i) = item {
+                    return next_state.select(MenuItem::ALL[i]);
+                }
+                continue;
+            }
+
+            // Auto-repeat is ignored so a key still held from the screen that
+            // led here does not act again.
+            let Some((code, true)) = event.button() else {
+                continue;
+            };
+            if !input.was_pressed(&code) {
+                continue;
+            }
+            let code = code.as_str();
+            if KEY_RESUME.contains(&code)
+                || self.controls.bindings().action(code) == Some(Action::Pause)
+            {
+                return next_state.select(MenuItem::Resume);
+            } else if KEY_UP.contains(&code) {
+                next_state.selected = (next_state.selected + rows - 1) % rows;
+            } else if KEY_DOWN.contains(&code) {
+                next_state.selected = (next_state.selected + 1) % rows;
+            } else if KEY_SELECT.contains(&code) {
+                let item = MenuItem::ALL[next_state.selected];
+                return next_state.select(item);
+            }
+        }
+        Transition::Stay(GameStateMachine::Paused(next_state))
+    }
+
+    fn on_enter(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_exit(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl From<Paused> for GameStateMachine {
+    fn from(val: Paused) -> Self {
+        GameStateMachine::Paused(val)
+    }
+}
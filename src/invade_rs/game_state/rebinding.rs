@@ -0,0 +1,222 @@
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use crate::engine::browser;
+use crate::engine::event::Event;
+use crate::engine::font::{Align, BitmapFont};
+use crate::engine::geometry::{Point, Rect};
+use crate::engine::input::InputState;
+use crate::engine::sprite::SpriteSheet;
+use crate::engine::DrawCommand;
+
+use super::super::character::layers;
+use super::super::controls::{key_label, Action, ControlScheme, Controls};
+use super::super::fsm::{State, Transition};
+use super::GameStateMachine;
+
+const SCREEN_RECT: Rect = Rect::new_from_x_y_w_h(0, 0, 600, 600);
+const PANEL_RECT: Rect = Rect::new_from_x_y_w_h(60, 120, 480, 370);
+const TITLE_Y: i16 = 150;
+const ROW_Y: i16 = 230;
+const ROW_SPACING: i16 = 36;
+const LABEL_X: i16 = 130;
+const KEYS_X: i16 = 260;
+const CURSOR_X: i16 = 90;
+const HELP_Y: i16 = 420;
+const HELP_SPACING: i16 = 24;
+
+// Navigation keys are fixed so that no rebinding can lock the player out of
+// this screen.
+const KEY_UP: [&str; 2] = ["ArrowUp", "PadDPadUp"];
+const KEY_DOWN: [&str; 2] = ["ArrowDown", "PadDPadDown"];
+const KEY_ADD: [&str; 2] = ["Enter", "PadSouth"];
+const KEY_CLEAR: [&str; 2] = ["Backspace", "PadWest"];
+const KEY_BACK: [&str; 3] = ["Escape", "PadEast", "PadStart"];
+// Only the keyboard can cancel, so every pad button stays bindable.
+const KEY_CANCEL: &str = "Escape";
+
+#[derive(Clone)]
+pub struct Rebinding {
+    sprite_sheet: Rc<SpriteSheet>,
+    controls: Rc<Controls>,
+    font: BitmapFont,
+    title_font: BitmapFont,
+    selected: usize,
+    listening: bool,
+}
+
+impl Rebinding {
+    pub fn new(sprite_sheet: Rc<SpriteSheet>, controls: Rc<Controls>) -> Self {
+        Self {
+            font: BitmapFont::new(sprite_sheet.clone()),
+            title_font: BitmapFont::new(sprite_sheet.clone()).with_scale(2),
+            sprite_sheet,
+            controls,
+            selected: 0,
+            listening: false,
+        }
+    }
+
+    // One row per action, then the control scheme toggle.
+    const ROWS: usize = Action::ALL.len() + 1;
+
+    fn selected_action(&self) -> Option<Action> {
+        Action::ALL.get(self.selected).copied()
+    }
+
+    fn toggle_scheme(&self) {
+        let scheme = match self.controls.scheme() {
+            ControlScheme::Keyboard => ControlScheme::Mouse,
+            ControlScheme::Mouse => ControlScheme::Keyboard,
+        };
+        self.controls.set_scheme(scheme);
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(err) = self.controls.save() {
+            browser::log(&format!("Could not save key bindings: {:#?}", err));
+        }
+    }
+
+    // Returns false once the player leaves the screen.
+    fn apply_key(&mut self, code: &str) -> bool {
+        if self.listening {
+            self.listening = false;
+            if code != KEY_CANCEL {
+                if let Some(action) = self.selected_action() {
+                    self.controls.bind(action, code);
+                    self.save();
+                }
+            }
+            return true;
+        }
+
+        if KEY_UP.contains(&code) {
+            self.selected = (self.selected + Self::ROWS - 1) % Self::ROWS;
+        } else if KEY_DOWN.contains(&code) {
+            self.selected = (self.selected + 1) % Self::ROWS;
+        } else if KEY_ADD.contains(&code) {
+            match self.selected_action() {
+                Some(_) => self.listening = true,
+                None => self.toggle_scheme(),
+            }
+        } else if KEY_CLEAR.contains(&code) {
+            if let Some(action) = self.selected_action() {
+                self.controls.clear(action);
+                self.save();
+            }
+        } else if KEY_BACK.contains(&code) {
+            return false;
+        }
+        true
+    }
+
+    pub fn draw(&self, _alpha: f32) -> Vec<DrawCommand> {
+        let mut draw_commands = vec![DrawCommand(
+            layers::HUD,
+            Box::new(|renderer| renderer.clear(&PANEL_RECT)),
+        )];
+        draw_commands.push(self.title_font.draw(
+            "CONTROLS",
+            &Point {
+                x: SCREEN_RECT.width() / 2,
+                y: TITLE_Y,
+            },
+            Align::Center,
+            layers::HUD,
+        ));
+
+        for (i, action) in Action::ALL.iter().enumerate() {
+            let y = ROW_Y + i as i16 * ROW_SPACING;
+            let keys = if self.listening && i == self.selected {
+                "PRESS A KEY".to_string()
+            } else {
+                let bindings = self.controls.bindings();
+                let labels: Vec<String> = bindings
+                    .codes(*action)
+                    .iter()
+                    .map(|code| key_label(code))
+                    .collect();
+                labels.join(" ")
+            };
+            draw_commands.push(self.text(action.label(), LABEL_X, y));
+            draw_commands.push(self.text(&keys, KEYS_X, y));
+        }
+
+        let y = ROW_Y + Action::ALL.len() as i16 * ROW_SPACING;
+        let mouse = match self.controls.scheme() {
+            ControlScheme::Keyboard => "OFF",
+            ControlScheme::Mouse => "ON",
+        };
+        draw_commands.push(self.text("MOUSE", LABEL_X, y));
+        draw_commands.push(self.text(mouse, KEYS_X, y));
+        draw_commands.push(self.draw_cursor());
+
+        draw_commands.push(self.text("ENTER ADD KEY OR TOGGLE  BKSP CLEAR", LABEL_X, HELP_Y));
+        draw_commands.push(self.text("ESC BACK", LABEL_X, HELP_Y + HELP_SPACING));
+        draw_commands
+    }
+
+    fn draw_cursor(&self) -> DrawCommand {
+        const CURSOR_OFFSET_Y: i16 = 8;
+
+        let cell = self
+            .sprite_sheet
+            .cell("rust_logo_white.png")
+            .expect("cell not found")
+            .clone();
+        let sprite_sheet = self.sprite_sheet.clone();
+        let position = Point {
+            x: CURSOR_X,
+            y: ROW_Y + self.selected as i16 * ROW_SPACING - CURSOR_OFFSET_Y,
+        };
+        DrawCommand(
+            layers::HUD,
+            Box::new(move |renderer| {
+                sprite_sheet.draw(renderer, &cell, &position);
+            }),
+        )
+    }
+
+    fn text(&self, text: &str, x: i16, y: i16) -> DrawCommand {
+        self.font
+            .draw(text, &Point { x, y }, Align::Left, layers::HUD)
+    }
+}
+
+impl State<Event, GameStateMachine> for Rebinding {
+    fn update(
+        &self,
+        _delta_ms: f32,
+        input: &InputState,
+        events: &[Event],
+    ) -> Transition<GameStateMachine> {
+        let mut next_state = self.clone();
+        for event in events {
+            // Skipping auto-repeat keeps the key that opened this screen from
+            // being taken as the first binding.
+            if let Some((code, true)) = event.button() {
+                if input.was_pressed(&code) && !next_state.apply_key(&code) {
+                    return Transition::Pop;
+                }
+            }
+        }
+        Transition::Stay(GameStateMachine::Rebinding(next_state))
+    }
+
+    fn on_enter(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_exit(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl From<Rebinding> for GameStateMachine {
+    fn from(val: Rebinding) -> Self {
+        GameStateMachine::Rebinding(val)
+    }
+}
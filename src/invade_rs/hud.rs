@@ -0,0 +1,142 @@
+use std::rc::Rc;
+
+use crate::engine::font::{Align, BitmapFont};
+use crate::engine::geometry::Point;
+use crate::engine::sprite::SpriteSheet;
+use crate::engine::DrawCommand;
+
+use super::character::layers;
+use super::controls::Action;
+use super::score::Score;
+use super::touch_controls::VirtualControls;
+
+const MARGIN: i16 = 20;
+const LABEL_Y: i16 = 10;
+const VALUE_Y: i16 = 28;
+const BANNER_SCALE: i16 = 2;
+const LIVES_Y: i16 = 19;
+const LIFE_ICON_SPACING: i16 = 34;
+const MAX_LIFE_ICONS: u32 = 4;
+
+#[derive(Clone)]
+pub struct Hud {
+    sprite_sheet: Rc<SpriteSheet>,
+    font: BitmapFont,
+    banner_font: BitmapFont,
+    screen_width: i16,
+}
+
+impl Hud {
+    pub fn new(sprite_sheet: Rc<SpriteSheet>, screen_width: i16) -> Self {
+        Self {
+            font: BitmapFont::new(sprite_sheet.clone()),
+            banner_font: BitmapFont::new(sprite_sheet.clone()).with_scale(BANNER_SCALE),
+            sprite_sheet,
+            screen_width,
+        }
+    }
+
+    pub fn draw(&self, score: &Score, lives: u32) -> Vec<DrawCommand> {
+        let left = MARGIN;
+        let right = self.screen_width - MARGIN;
+
+        let mut draw_commands = vec![
+            self.text("SCORE", left, LABEL_Y, Align::Left),
+            self.text(&format_points(score.current()), left, VALUE_Y, Align::Left),
+            self.text("HI SCORE", right, LABEL_Y, Align::Right),
+            self.text(&format_points(score.high()), right, VALUE_Y, Align::Right),
+        ];
+        draw_commands.append(&mut self.draw_lives(lives));
+        draw_commands
+    }
+
+    // The ship in play is not counted, so only the reserve lives get an icon.
+    fn draw_lives(&self, lives: u32) -> Vec<DrawCommand> {
+        let icons = lives.saturating_sub(1).min(MAX_LIFE_ICONS) as i16;
+        let x_origin = self.screen_width / 2 - (icons * LIFE_ICON_SPACING) / 2;
+
+        let cell = self
+            .sprite_sheet
+            .cell("rust_logo_orange.png")
+            .expect("cell not found")
+            .clone();
+        let sprite_sheet = self.sprite_sheet.clone();
+        let icons_command = DrawCommand(
+            layers::HUD,
+            Box::new(move |renderer| {
+                for i in 0..icons {
+                    let position = Point {
+                        x: x_origin + i * LIFE_ICON_SPACING,
+                        y: LABEL_Y,
+                    };
+                    sprite_sheet.draw(renderer, &cell, &position);
+                }
+            }),
+        );
+
+        vec![
+            self.text(&lives.to_string(), x_origin - 8, LIVES_Y, Align::Right),
+            icons_command,
+        ]
+    }
+
+    // Each zone is labelled along its bottom edge, with the fire button drawn
+    // as a logo that lights up while held.
+    pub fn draw_touch_controls(&self, touch: &VirtualControls) -> Vec<DrawCommand> {
+        const LABEL_OFFSET_Y: i16 = 24;
+
+        let mut draw_commands = vec![];
+        for (zone, action) in VirtualControls::ZONES.iter() {
+            let center = zone.center();
+            let label_y = zone.bottom() - LABEL_OFFSET_Y;
+            draw_commands.push(self.text(action.label(), center.x, label_y, Align::Center));
+
+            if *action == Action::Fire {
+                let cell_name = if touch.is_held(*action) {
+                    "rust_logo_orange.png"
+                } else {
+                    "rust_logo_white.png"
+                };
+                let cell = self
+                    .sprite_sheet
+                    .cell(cell_name)
+                    .expect("cell not found")
+                    .clone();
+                let shape = cell.shape();
+                let position = Point {
+                    x: center.x - shape.width / 2,
+                    y: center.y - shape.height / 2,
+                };
+                let sprite_sheet = self.sprite_sheet.clone();
+                draw_commands.push(DrawCommand(
+                    layers::HUD,
+                    Box::new(move |renderer| {
+                        sprite_sheet.draw(renderer, &cell, &position);
+                    }),
+                ));
+            }
+        }
+        draw_commands
+    }
+
+    pub fn draw_banner(&self, text: &str, y: i16) -> DrawCommand {
+        let position = Point {
+            x: self.screen_width / 2,
+            y,
+        };
+        self.banner_font
+            .draw(text, &position, Align::Center, layers::HUD)
+    }
+
+    pub fn draw_hint(&self, text: &str, y: i16) -> DrawCommand {
+        self.text(text, self.screen_width / 2, y, Align::Center)
+    }
+
+    fn text(&self, text: &str, x: i16, y: i16, align: Align) -> DrawCommand {
+        self.font.draw(text, &Point { x, y }, align, layers::HUD)
+    }
+}
+
+fn format_points(points: u32) -> String {
+    format!("{:05}", points)
+}
@@ -0,0 +1,151 @@
+use crate::engine::geometry::{Point, Rect, Shape};
+use crate::engine::image::Image;
+use crate::engine::mask::Mask;
+use crate::engine::random::Rng;
+use crate::engine::DrawCommand;
+
+use super::character::{layers, GameCharacter, GameCommand, Id};
+
+pub enum Crater {
+    Blast(Point),
+    Area(Rect),
+}
+
+#[derive(Clone)]
+pub struct MaskShield {
+    id: Id,
+    position: Point,
+    mask: Mask,
+    image: Image,
+}
+
+impl MaskShield {
+    const WIDTH: i16 = 64;
+    const HEIGHT: i16 = 48;
+    const CORNER: i16 = 12;
+    const ARCH_WIDTH: i16 = 24;
+    const ARCH_HEIGHT: i16 = 16;
+    const COLOR: [u8; 4] = [0xff, 0x00, 0x00, 0xff];
+    const BLAST: [&'static str; 8] = [
+        "#...#..#", "..#...#.", ".######.", "########", "########", ".######.", "..#..#..",
+        "#..#...#",
+    ];
+
+    pub fn get_shape() -> Shape {
+        Shape {
+            width: Self::WIDTH,
+            height: Self::HEIGHT,
+        }
+    }
+
+    pub fn new(position: Point) -> Self {
+        let mask = Self::new_bunker();
+        let image = mask.to_bitmap(Self::COLOR).into();
+
+        Self {
+            id: Id::new(),
+            position,
+            mask,
+            image,
+        }
+    }
+
+    fn new_bunker() -> Mask {
+        let (width, height) = (Self::WIDTH as i32, Self::HEIGHT as i32);
+        let corner = Self::CORNER as i32;
+        let arch_left = (width - Self::ARCH_WIDTH as i32) / 2;
+        let arch_right = arch_left + Self::ARCH_WIDTH as i32;
+        let arch_top = height - Self::ARCH_HEIGHT as i32;
+
+        Mask::from_fn(width as u32, height as u32, |x, y| {
+            let (x, y) = (x as i32, y as i32);
+            let in_corner = y < corner && (x < corner - y || width - corner + y <= x);
+            let in_arch = arch_top <= y && arch_left <= x && x < arch_right;
+            !in_corner && !in_arch
+        })
+    }
+
+    pub fn id(&self) -> &Id {
+        &self.id
+    }
+
+    pub fn bounding_box(&self) -> Rect {
+        Rect::new(self.position, Self::get_shape())
+    }
+
+    pub fn overlaps(&self, rect: &Rect) -> bool {
+        self.mask.overlaps(&self.to_local(rect))
+    }
+
+    pub fn is_worn_out(&self) -> bool {
+        self.mask.is_empty()
+    }
+
+    pub fn erode(&mut self, crater: &Crater) {
+        match crater {
+            Crater::Blast(center) => {
+                let blast = Mask::from_pattern(&Self::BLAST);
+                let origin = Point {
+                    x: center.x - self.position.x - blast.width() as i16 / 2,
+                    y: center.y - self.position.y - blast.height() as i16 / 2,
+                };
+                self.mask.erase(&blast, &origin);
+            }
+            Crater::Area(rect) => self.mask.clear_rect(&self.to_local(rect)),
+        }
+        self.image = self.mask.to_bitmap(Self::COLOR).into();
+    }
+
+    pub fn update(&mut self, _delta_ms: f32, _rng: &mut Rng) -> Option<GameCommand> {
+        None
+    }
+
+    pub fn draw(&self, _alpha: f32) -> Option<DrawCommand> {
+        let image = self.image.clone();
+        let source = Rect::new(Point::default(), Self::get_shape());
+        let destination = self.bounding_box();
+
+        Some(DrawCommand(
+            layers::SHIELD,
+            Box::new(move |renderer| {
+                renderer.draw_image(&image, &source, &destination);
+            }),
+        ))
+    }
+
+    pub fn on_exit_screen(&mut self) -> Option<GameCommand> {
+        Some(GameCommand::DestroyCharacter(self.id().clone()))
+    }
+
+    pub fn on_collide(&self, other: &GameCharacter) -> Option<GameCommand> {
+        // Beams fall onto the shield and missiles rise into it, so each blasts
+        // the first occupied pixel it meets along its direction of travel.
+        let rect = other.bounding_box();
+        let local = self.to_local(&rect);
+        let crater = match other {
+            GameCharacter::Beam(_) => Crater::Blast(self.to_world(self.mask.topmost_hit(&local)?)),
+            GameCharacter::Missile(_) => {
+                Crater::Blast(self.to_world(self.mask.bottommost_hit(&local)?))
+            }
+            GameCharacter::Ferris(_) => Crater::Area(rect),
+            _ => return None,
+        };
+        Some(GameCommand::ErodeShield(self.id().clone(), crater))
+    }
+
+    fn to_local(&self, rect: &Rect) -> Rect {
+        Rect::new_from_x_y_w_h(
+            rect.x() - self.position.x,
+            rect.y() - self.position.y,
+            rect.width(),
+            rect.height(),
+        )
+    }
+
+    fn to_world(&self, point: Point) -> Point {
+        Point {
+            x: point.x + self.position.x,
+            y: point.y + self.position.y,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
 use crate::engine::geometry::{Point, Rect, Shape};
+use crate::engine::random::Rng;
 use crate::engine::sprite::{Cell, SpriteSheet};
 use crate::engine::DrawCommand;
 
@@ -10,6 +11,7 @@ use super::character::{layers, GameCharacter, GameCommand, Id};
 pub struct Missile {
     id: Id,
     position: Point,
+    last_position: Point,
     sprite_sheet: Rc<SpriteSheet>,
     cell: Cell,
     velocity: f32,
@@ -31,6 +33,7 @@ impl Missile {
         Self {
             id: Id::new(),
             position,
+            last_position: position,
             sprite_sheet,
             cell,
             velocity: Self::DEFAULT_VELOCITY,
@@ -45,16 +48,17 @@ impl Missile {
         Rect::new(self.position, self.cell.shape())
     }
 
-    pub fn update(&mut self, delta_ms: f32) -> Option<GameCommand> {
+    pub fn update(&mut self, delta_ms: f32, _rng: &mut Rng) -> Option<GameCommand> {
+        self.last_position = self.position;
         self.position.y -= (self.velocity * delta_ms).round() as i16;
 
         None
     }
 
-    pub fn draw(&self) -> Option<DrawCommand> {
+    pub fn draw(&self, alpha: f32) -> Option<DrawCommand> {
         let cell = self.cell.clone();
         let sprite_sheet = self.sprite_sheet.clone();
-        let position = self.position;
+        let position = self.last_position.lerp(&self.position, alpha);
 
         Some(DrawCommand(
             layers::MISSILE,
@@ -65,15 +69,16 @@ impl Missile {
     }
 
     pub fn on_exit_screen(&mut self) -> Option<GameCommand> {
-        Some(GameCommand::DestroyCharacter(self.id().clone()))
+        Some(GameCommand::ExplodeCharacter(self.id().clone()))
     }
 
     pub fn on_collide(&self, other: &GameCharacter) -> Option<GameCommand> {
         match other {
             GameCharacter::Ferris(_)
             | GameCharacter::TurboFish(_)
-            | GameCharacter::ShieldElement(_) => {
-                Some(GameCommand::DestroyCharacter(self.id().clone()))
+            | GameCharacter::ShieldElement(_)
+            | GameCharacter::MaskShield(_) => {
+                Some(GameCommand::ExplodeCharacter(self.id().clone()))
             }
             _ => None,
         }
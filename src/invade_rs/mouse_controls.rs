@@ -0,0 +1,58 @@
+use crate::engine::event::{Event, Pointer, PointerButton, PointerKind};
+
+// Follows the mouse for the mouse control scheme: where it last was over the
+// canvas and whether the primary button is down.
+#[derive(Clone, Default)]
+pub struct MouseControls {
+    x: Option<i16>,
+    held: bool,
+    pressed: bool,
+}
+
+impl MouseControls {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, events: &[Event]) {
+        self.pressed = false;
+        for event in events {
+            match event {
+                Event::PointerMove(pointer) if Self::is_mouse(pointer) => {
+                    self.x = Some(pointer.position.x);
+                }
+                Event::PointerDown(pointer) if Self::is_mouse(pointer) => {
+                    self.x = Some(pointer.position.x);
+                    if pointer.button == Some(PointerButton::Primary) {
+                        self.pressed |= !self.held;
+                        self.held = true;
+                    }
+                }
+                Event::PointerUp(pointer)
+                    if Self::is_mouse(pointer)
+                        && pointer.button == Some(PointerButton::Primary) =>
+                {
+                    self.held = false;
+                }
+                event if event.is_focus_loss() => self.held = false,
+                _ => {}
+            }
+        }
+    }
+
+    fn is_mouse(pointer: &Pointer) -> bool {
+        pointer.kind == PointerKind::Mouse
+    }
+
+    pub fn x(&self) -> Option<i16> {
+        self.x
+    }
+
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
+    pub fn was_pressed(&self) -> bool {
+        self.pressed
+    }
+}
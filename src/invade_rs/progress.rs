@@ -0,0 +1,26 @@
+use super::config::Config;
+use super::score::Score;
+
+#[derive(Clone, Copy)]
+pub struct Progress {
+    pub score: Score,
+    pub wave: u32,
+    pub lives: u32,
+}
+
+impl Progress {
+    pub fn new(config: &Config, score: Score) -> Self {
+        Self {
+            score,
+            wave: 1,
+            lives: config.lives,
+        }
+    }
+
+    pub fn next_wave(&self) -> Self {
+        Self {
+            wave: self.wave + 1,
+            ..*self
+        }
+    }
+}
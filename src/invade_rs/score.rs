@@ -0,0 +1,24 @@
+#[derive(Clone, Copy, Default)]
+pub struct Score {
+    current: u32,
+    high: u32,
+}
+
+impl Score {
+    pub fn new(high: u32) -> Self {
+        Self { current: 0, high }
+    }
+
+    pub fn add(&mut self, points: u32) {
+        self.current += points;
+        self.high = self.high.max(self.current);
+    }
+
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    pub fn high(&self) -> u32 {
+        self.high
+    }
+}
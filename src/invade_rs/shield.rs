@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
 use crate::engine::geometry::{Point, Rect, Shape};
+use crate::engine::random::Rng;
 use crate::engine::sprite::{Cell, SpriteSheet};
 use crate::engine::DrawCommand;
 
@@ -19,9 +20,18 @@ pub struct ShieldElement {
     position: Point,
     sprite_sheet: Rc<SpriteSheet>,
     cell: Cell,
+    hit_points: u32,
 }
 
 impl ShieldElement {
+    const DAMAGE_CELLS: [&'static str; 3] =
+        ["shield_red_1.png", "shield_red_2.png", "shield_red_3.png"];
+    const MAX_HIT_POINTS: u32 = Self::DAMAGE_CELLS.len() as u32 + 1;
+    // Beams fall onto the shield from above and knock it two damage cells
+    // on, while the player's own missiles only scrape the underside.
+    const BEAM_DAMAGE: u32 = 2;
+    const MISSILE_DAMAGE: u32 = 1;
+
     pub fn get_shape(sprite_sheet: &Rc<SpriteSheet>) -> Shape {
         let cell = sprite_sheet
             .cell("shield_red_0.png")
@@ -45,8 +55,32 @@ impl ShieldElement {
             position,
             sprite_sheet,
             cell,
+            hit_points: Self::MAX_HIT_POINTS,
         }
     }
+
+    pub fn is_worn_out(&self) -> bool {
+        self.hit_points == 0
+    }
+
+    // Every hit point lost moves the element, corners included, one damage
+    // cell further on.
+    pub fn damage(&mut self, damage: u32) {
+        self.hit_points = self.hit_points.saturating_sub(damage);
+        if self.is_worn_out() {
+            return;
+        }
+
+        let stage = (Self::MAX_HIT_POINTS - self.hit_points) as usize;
+        if 0 < stage {
+            self.cell = self
+                .sprite_sheet
+                .cell(Self::DAMAGE_CELLS[stage - 1])
+                .expect("cell not found")
+                .clone();
+        }
+    }
+
     pub fn id(&self) -> &Id {
         &self.id
     }
@@ -55,11 +89,11 @@ impl ShieldElement {
         Rect::new(self.position, self.cell.shape())
     }
 
-    pub fn update(&mut self, _delta_ms: f32) -> Option<GameCommand> {
+    pub fn update(&mut self, _delta_ms: f32, _rng: &mut Rng) -> Option<GameCommand> {
         None
     }
 
-    pub fn draw(&self) -> Option<DrawCommand> {
+    pub fn draw(&self, _alpha: f32) -> Option<DrawCommand> {
         let cell = self.cell.clone();
         let sprite_sheet = self.sprite_sheet.clone();
         let position = self.position;
@@ -78,9 +112,15 @@ impl ShieldElement {
 
     pub fn on_collide(&self, other: &GameCharacter) -> Option<GameCommand> {
         match other {
-            GameCharacter::Beam(_) | GameCharacter::Ferris(_) => {
-                Some(GameCommand::DestroyCharacter(self.id().clone()))
-            }
+            GameCharacter::Beam(_) => Some(GameCommand::DamageShield(
+                self.id().clone(),
+                Self::BEAM_DAMAGE,
+            )),
+            GameCharacter::Missile(_) => Some(GameCommand::DamageShield(
+                self.id().clone(),
+                Self::MISSILE_DAMAGE,
+            )),
+            GameCharacter::Ferris(_) => Some(GameCommand::DestroyCharacter(self.id().clone())),
             _ => None,
         }
     }
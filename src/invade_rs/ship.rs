@@ -1,25 +1,44 @@
 use std::rc::Rc;
 
 use crate::engine::geometry::{Point, Rect, Shape};
+use crate::engine::random::Rng;
+use crate::engine::sequence::{Frame, PlaybackMode, Sequence};
 use crate::engine::sprite::{Cell, SpriteSheet};
 use crate::engine::DrawCommand;
 
 use super::character::{layers, GameCharacter, GameCommand, Id};
 use super::missile::Missile;
 
+#[derive(Clone)]
+enum ShipState {
+    Active,
+    Invulnerable(f32),
+    Exploding(Sequence),
+    Respawning(f32),
+}
+
 #[derive(Clone)]
 pub struct Ship {
     id: Id,
     position: Point,
+    last_position: Point,
+    spawn_position: Point,
     sprite_sheet: Rc<SpriteSheet>,
     cell: Cell,
     velocity: f32,
+    target_x: Option<i16>,
     need_shot: bool,
     has_bullet: bool,
+    state: ShipState,
 }
 
 impl Ship {
     const DEFAULT_VELOCITY: f32 = 90.0 / 1000.0;
+    const EXPLOSION_LOOPS: u32 = 6;
+    const EXPLOSION_SCALE: i16 = 3;
+    const RESPAWN_DELAY: f32 = 1000.0;
+    const INVULNERABLE_DURATION: f32 = 2000.0;
+    const BLINK_INTERVAL: f32 = 100.0;
 
     pub fn get_shape(sprite_sheet: &Rc<SpriteSheet>) -> Shape {
         let cell = Self::get_cell(sprite_sheet).expect("cell not found");
@@ -34,24 +53,71 @@ impl Ship {
         Self {
             id: Id::new(),
             position,
+            last_position: position,
+            spawn_position: position,
             sprite_sheet,
             cell,
             velocity: 0.0,
+            target_x: None,
             need_shot: false,
             has_bullet: true,
+            state: ShipState::Active,
         }
     }
 
+    fn new_explosion() -> Sequence {
+        const EXPLOSION_ANIMATION_FRAMES: usize = 2;
+
+        let frames = (0..EXPLOSION_ANIMATION_FRAMES).map(|i| {
+            let cell_name = format!("explode_{}.png", i);
+            let duration = 100.0;
+            Frame::new(cell_name, duration)
+        });
+        Sequence::new(frames.collect()).with_mode(PlaybackMode::LoopTimes(Self::EXPLOSION_LOOPS))
+    }
+
+    pub fn is_alive(&self) -> bool {
+        matches!(self.state, ShipState::Active | ShipState::Invulnerable(_))
+    }
+
+    pub fn is_vulnerable(&self) -> bool {
+        matches!(self.state, ShipState::Active)
+    }
+
+    pub fn explode(&mut self) {
+        self.state = ShipState::Exploding(Self::new_explosion());
+    }
+
+    pub fn respawn(&mut self) {
+        self.position = self.spawn_position;
+        self.last_position = self.spawn_position;
+        self.velocity = 0.0;
+        self.target_x = None;
+        self.need_shot = false;
+        self.has_bullet = true;
+        self.state = ShipState::Invulnerable(Self::INVULNERABLE_DURATION);
+    }
+
     pub fn move_left(&mut self) {
         self.velocity = -Self::DEFAULT_VELOCITY;
+        self.target_x = None;
     }
 
     pub fn move_right(&mut self) {
         self.velocity = Self::DEFAULT_VELOCITY;
+        self.target_x = None;
     }
 
     pub fn stop(&mut self) {
         self.velocity = 0.0;
+        self.target_x = None;
+    }
+
+    // Steers the ship's center towards `x` at its usual speed, stopping there
+    // instead of overshooting.
+    pub fn seek(&mut self, x: i16) {
+        self.velocity = 0.0;
+        self.target_x = Some(x);
     }
 
     pub fn shot(&mut self) {
@@ -80,8 +146,40 @@ impl Ship {
         Rect::new(self.position, self.cell.shape())
     }
 
-    pub fn update(&mut self, delta_ms: f32) -> Option<GameCommand> {
-        self.position.x += (self.velocity * delta_ms).round() as i16;
+    pub fn update(&mut self, delta_ms: f32, _rng: &mut Rng) -> Option<GameCommand> {
+        self.last_position = self.position;
+
+        match &mut self.state {
+            ShipState::Exploding(animation) => {
+                animation.update(delta_ms);
+                if animation.finished() {
+                    self.state = ShipState::Respawning(Self::RESPAWN_DELAY);
+                }
+                return None;
+            }
+            ShipState::Respawning(remaining) => {
+                *remaining -= delta_ms;
+                if *remaining <= 0.0 {
+                    return Some(GameCommand::RespawnPlayer);
+                }
+                return None;
+            }
+            ShipState::Invulnerable(remaining) => {
+                *remaining -= delta_ms;
+                if *remaining <= 0.0 {
+                    self.state = ShipState::Active;
+                }
+            }
+            ShipState::Active => {}
+        }
+
+        self.position.x += match self.target_x {
+            Some(target_x) => {
+                let max_step = (Self::DEFAULT_VELOCITY * delta_ms).round() as i16;
+                (target_x - self.bounding_box().center().x).clamp(-max_step, max_step)
+            }
+            None => (self.velocity * delta_ms).round() as i16,
+        };
 
         if !self.need_shot {
             return None;
@@ -92,10 +190,21 @@ impl Ship {
         Some(GameCommand::SpawnCharacter(missile.into()))
     }
 
-    pub fn draw(&self) -> Option<DrawCommand> {
+    pub fn draw(&self, alpha: f32) -> Option<DrawCommand> {
+        match &self.state {
+            ShipState::Exploding(animation) => return self.draw_explosion(animation),
+            ShipState::Respawning(_) => return None,
+            ShipState::Invulnerable(remaining)
+                if (remaining / Self::BLINK_INTERVAL) as i32 % 2 == 1 =>
+            {
+                return None
+            }
+            _ => {}
+        }
+
         let cell = self.cell.clone();
         let sprite_sheet = self.sprite_sheet.clone();
-        let position = self.position;
+        let position = self.last_position.lerp(&self.position, alpha);
 
         Some(DrawCommand(
             layers::SHIP,
@@ -105,11 +214,39 @@ impl Ship {
         ))
     }
 
+    fn draw_explosion(&self, animation: &Sequence) -> Option<DrawCommand> {
+        let cell = self
+            .sprite_sheet
+            .cell(animation.current_frame_cell_name())
+            .expect("cell not found")
+            .clone();
+        let sprite_sheet = self.sprite_sheet.clone();
+        let ship_shape = self.cell.shape();
+        let explosion_shape = cell.shape();
+        let position = Point {
+            x: self.position.x
+                + (ship_shape.width - explosion_shape.width * Self::EXPLOSION_SCALE) / 2,
+            y: self.position.y
+                + (ship_shape.height - explosion_shape.height * Self::EXPLOSION_SCALE) / 2,
+        };
+
+        Some(DrawCommand(
+            layers::SHIP,
+            Box::new(move |renderer| {
+                sprite_sheet.draw_scaled(renderer, &cell, &position, Self::EXPLOSION_SCALE);
+            }),
+        ))
+    }
+
     pub fn on_exit_screen(&mut self) -> Option<GameCommand> {
         Some(GameCommand::DestroyCharacter(self.id().clone()))
     }
 
     pub fn on_collide(&self, other: &GameCharacter) -> Option<GameCommand> {
+        if !self.is_vulnerable() {
+            return None;
+        }
+
         match other {
             GameCharacter::Beam(_) | GameCharacter::Ferris(_) => Some(GameCommand::DestroyPlayer),
             _ => None,
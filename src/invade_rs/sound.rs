@@ -0,0 +1,222 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::engine::audio::sequencer::StepSequencer;
+use crate::engine::audio::sfx::{Envelope, SfxParams, Waveform};
+use crate::engine::audio::{AudioBackend, Playback, Sound};
+use crate::engine::browser;
+
+use super::ferris::Ferris;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+    Shoot,
+    EnemyShoot,
+    EnemyDeath,
+    Impact,
+    ShieldHit,
+    UfoFlyby,
+    PlayerDeath,
+    Respawn,
+}
+
+impl SoundEffect {
+    const ALL: [SoundEffect; 8] = [
+        SoundEffect::Shoot,
+        SoundEffect::EnemyShoot,
+        SoundEffect::EnemyDeath,
+        SoundEffect::Impact,
+        SoundEffect::ShieldHit,
+        SoundEffect::UfoFlyby,
+        SoundEffect::PlayerDeath,
+        SoundEffect::Respawn,
+    ];
+
+    fn params(&self) -> SfxParams {
+        let defaults = SfxParams::default();
+        match self {
+            SoundEffect::Shoot => SfxParams {
+                envelope: envelope(0.0, 0.04, 0.08),
+                frequency: 880.0,
+                frequency_slide: -2400.0,
+                min_frequency: 200.0,
+                volume: 0.25,
+                ..defaults
+            },
+            SoundEffect::EnemyShoot => SfxParams {
+                waveform: Waveform::Sawtooth,
+                envelope: envelope(0.0, 0.03, 0.07),
+                frequency: 300.0,
+                frequency_slide: -600.0,
+                volume: 0.15,
+                ..defaults
+            },
+            SoundEffect::EnemyDeath => SfxParams {
+                waveform: Waveform::Noise,
+                envelope: envelope(0.0, 0.05, 0.2),
+                frequency: 2000.0,
+                frequency_slide: -4000.0,
+                min_frequency: 300.0,
+                volume: 0.4,
+                ..defaults
+            },
+            SoundEffect::Impact => SfxParams {
+                waveform: Waveform::Noise,
+                envelope: envelope(0.0, 0.02, 0.06),
+                frequency: 1200.0,
+                frequency_slide: -3000.0,
+                volume: 0.25,
+                ..defaults
+            },
+            SoundEffect::ShieldHit => SfxParams {
+                waveform: Waveform::Noise,
+                envelope: envelope(0.0, 0.01, 0.05),
+                frequency: 600.0,
+                frequency_slide: -1000.0,
+                volume: 0.2,
+                ..defaults
+            },
+            // Loops seamlessly: the sound lasts exactly four vibrato cycles.
+            SoundEffect::UfoFlyby => SfxParams {
+                envelope: envelope(0.0, 0.5, 0.0),
+                frequency: 600.0,
+                vibrato_depth: 0.25,
+                vibrato_speed: 8.0,
+                volume: 0.2,
+                ..defaults
+            },
+            SoundEffect::PlayerDeath => SfxParams {
+                waveform: Waveform::Noise,
+                envelope: envelope(0.0, 0.3, 0.6),
+                frequency: 800.0,
+                frequency_slide: -600.0,
+                min_frequency: 60.0,
+                vibrato_depth: 0.3,
+                vibrato_speed: 12.0,
+                volume: 0.45,
+                ..defaults
+            },
+            SoundEffect::Respawn => SfxParams {
+                envelope: envelope(0.0, 0.1, 0.1),
+                frequency: 400.0,
+                frequency_slide: 1600.0,
+                volume: 0.2,
+                ..defaults
+            },
+        }
+    }
+}
+
+// The four descending bass notes of the fleet's march.
+const MARCH_NOTES: [f32; 4] = [98.0, 92.5, 87.3, 82.4];
+
+fn march_note(frequency: f32) -> SfxParams {
+    SfxParams {
+        envelope: envelope(0.0, 0.05, 0.08),
+        frequency,
+        volume: 0.35,
+        ..SfxParams::default()
+    }
+}
+
+fn envelope(attack: f32, sustain: f32, decay: f32) -> Envelope {
+    Envelope {
+        attack,
+        sustain,
+        decay,
+    }
+}
+
+pub struct SoundBank {
+    backend: Rc<dyn AudioBackend>,
+    sounds: HashMap<SoundEffect, Sound>,
+    loops: RefCell<HashMap<SoundEffect, Playback>>,
+    march: RefCell<StepSequencer>,
+}
+
+impl SoundBank {
+    const SAMPLE_RATE: u32 = 22050;
+
+    // Effects that fail to upload stay silent rather than stopping the game.
+    pub fn new(backend: Rc<dyn AudioBackend>) -> Self {
+        let mut sounds = HashMap::new();
+        for effect in SoundEffect::ALL {
+            if let Some(sound) = Self::upload(&backend, &effect.params()) {
+                sounds.insert(effect, sound);
+            }
+        }
+
+        let march_notes = MARCH_NOTES
+            .iter()
+            .filter_map(|frequency| Self::upload(&backend, &march_note(*frequency)))
+            .collect();
+        let march = StepSequencer::new(march_notes, Ferris::ANIMATION_CYCLE);
+
+        Self {
+            backend,
+            sounds,
+            loops: RefCell::new(HashMap::new()),
+            march: RefCell::new(march),
+        }
+    }
+
+    fn upload(backend: &Rc<dyn AudioBackend>, params: &SfxParams) -> Option<Sound> {
+        let buffer = params.synthesize(Self::SAMPLE_RATE);
+        match backend.upload(&buffer) {
+            Ok(sound) => Some(sound),
+            Err(err) => {
+                browser::log(&format!("Could not upload sound: {:#?}", err));
+                None
+            }
+        }
+    }
+
+    pub fn play(&self, effect: SoundEffect) {
+        if let Some(sound) = self.sounds.get(&effect) {
+            self.backend.play(*sound).ok();
+        }
+    }
+
+    pub fn set_looping(&self, effect: SoundEffect, playing: bool) {
+        let mut loops = self.loops.borrow_mut();
+        match (loops.get(&effect).copied(), playing) {
+            (None, true) => {
+                let Some(sound) = self.sounds.get(&effect) else {
+                    return;
+                };
+                if let Ok(playback) = self.backend.play_looping(*sound) {
+                    loops.insert(effect, playback);
+                }
+            }
+            (Some(playback), false) => {
+                self.backend.stop(playback).ok();
+                loops.remove(&effect);
+            }
+            _ => {}
+        }
+    }
+
+    // Runs on the fleet's clock: `tempo` is the fleet speed multiplier, and the
+    // march rests whenever the fleet is frozen.
+    pub fn update_march(&self, delta: f32, tempo: f32, playing: bool) {
+        let mut march = self.march.borrow_mut();
+        if playing {
+            march.resume();
+        } else {
+            march.pause();
+        }
+        march.set_tempo(tempo);
+        march.update(delta, self.backend.as_ref()).ok();
+    }
+
+    pub fn restart_march(&self) {
+        self.march.borrow_mut().reset();
+    }
+
+    pub fn stop_all(&self) {
+        for (_, playback) in self.loops.borrow_mut().drain() {
+            self.backend.stop(playback).ok();
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use crate::engine::event::{Event, Pointer, PointerKind};
+use crate::engine::geometry::{Point, Rect};
+
+use super::controls::Action;
+
+// On-screen buttons for phones and tablets. Each touch belongs to the zone it
+// is currently over, so sliding a thumb from LEFT to RIGHT turns the ship
+// around without lifting it.
+#[derive(Clone, Default)]
+pub struct VirtualControls {
+    touches: HashMap<i32, Option<Action>>,
+    pressed: Vec<Action>,
+    enabled: bool,
+}
+
+impl VirtualControls {
+    pub const ZONES: [(Rect, Action); 3] = [
+        (Rect::new_from_x_y_w_h(0, 440, 150, 160), Action::MoveLeft),
+        (
+            Rect::new_from_x_y_w_h(150, 440, 150, 160),
+            Action::MoveRight,
+        ),
+        (Rect::new_from_x_y_w_h(420, 440, 180, 160), Action::Fire),
+    ];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The overlay stays hidden until the first touch, so desktop players
+    // never see it.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn update(&mut self, events: &[Event]) {
+        self.pressed.clear();
+        for event in events {
+            match event {
+                Event::PointerDown(pointer) if Self::is_touch(pointer) => {
+                    self.enabled = true;
+                    self.touch(pointer);
+                }
+                Event::PointerMove(pointer) if self.touches.contains_key(&pointer.id) => {
+                    self.touch(pointer);
+                }
+                Event::PointerUp(pointer) => {
+                    self.touches.remove(&pointer.id);
+                }
+                event if event.is_focus_loss() => self.touches.clear(),
+                _ => {}
+            }
+        }
+    }
+
+    fn touch(&mut self, pointer: &Pointer) {
+        let action = Self::zone_at(&pointer.position);
+        let previous = self.touches.insert(pointer.id, None).flatten();
+        if let Some(action) = action {
+            if previous != Some(action) && !self.is_held(action) {
+                self.pressed.push(action);
+            }
+        }
+        self.touches.insert(pointer.id, action);
+    }
+
+    fn is_touch(pointer: &Pointer) -> bool {
+        matches!(pointer.kind, PointerKind::Touch | PointerKind::Pen)
+    }
+
+    fn zone_at(position: &Point) -> Option<Action> {
+        Self::ZONES
+            .iter()
+            .find(|(zone, _)| zone.contains(position))
+            .map(|(_, action)| *action)
+    }
+
+    pub fn is_held(&self, action: Action) -> bool {
+        self.touches.values().any(|held| *held == Some(action))
+    }
+
+    pub fn was_pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+}
@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
 use crate::engine::geometry::{Point, Rect, Shape};
+use crate::engine::random::Rng;
 use crate::engine::sequence::{Frame, Sequence};
 use crate::engine::sprite::SpriteSheet;
 use crate::engine::DrawCommand;
@@ -11,12 +12,15 @@ use super::character::{layers, GameCharacter, GameCommand, Id};
 pub struct TurboFish {
     id: Id,
     position: Point,
+    last_position: Point,
     sprite_sheet: Rc<SpriteSheet>,
     animation: Sequence,
+    points: u32,
 }
 
 impl TurboFish {
     const DEFAULT_VELOCITY: f32 = 100.0 / 1000.0;
+    const MYSTERY_POINTS: [u32; 4] = [50, 100, 150, 300];
 
     pub fn get_shape(sprite_sheet: &Rc<SpriteSheet>) -> Shape {
         let cell = sprite_sheet
@@ -25,14 +29,18 @@ impl TurboFish {
         cell.shape()
     }
 
-    pub fn new(sprite_sheet: Rc<SpriteSheet>, position: Point) -> Self {
+    pub fn new(sprite_sheet: Rc<SpriteSheet>, position: Point, rng: &mut Rng) -> Self {
         let animation = Self::new_animation();
+        let points =
+            Self::MYSTERY_POINTS[rng.gen_range(0..Self::MYSTERY_POINTS.len() as u32) as usize];
 
         Self {
             id: Id::new(),
             position,
+            last_position: position,
             sprite_sheet,
             animation,
+            points,
         }
     }
 
@@ -47,6 +55,10 @@ impl TurboFish {
         Sequence::new(frames.collect())
     }
 
+    pub fn points(&self) -> u32 {
+        self.points
+    }
+
     pub fn id(&self) -> &Id {
         &self.id
     }
@@ -54,21 +66,22 @@ impl TurboFish {
         Rect::new(self.position, Self::get_shape(&self.sprite_sheet))
     }
 
-    pub fn update(&mut self, delta_ms: f32) -> Option<GameCommand> {
+    pub fn update(&mut self, delta_ms: f32, _rng: &mut Rng) -> Option<GameCommand> {
+        self.last_position = self.position;
         self.animation.update(delta_ms);
         self.position.x += (Self::DEFAULT_VELOCITY * delta_ms).round() as i16;
 
         None
     }
 
-    pub fn draw(&self) -> Option<DrawCommand> {
+    pub fn draw(&self, alpha: f32) -> Option<DrawCommand> {
         let cell = self
             .sprite_sheet
             .cell(self.animation.current_frame_cell_name())
             .expect("cell not found")
             .clone();
         let sprite_sheet = self.sprite_sheet.clone();
-        let position = self.position;
+        let position = self.last_position.lerp(&self.position, alpha);
 
         Some(DrawCommand(
             layers::ENEMY,
@@ -84,7 +97,9 @@ impl TurboFish {
 
     pub fn on_collide(&self, other: &GameCharacter) -> Option<GameCommand> {
         match other {
-            GameCharacter::Missile(_) => Some(GameCommand::DestroyCharacter(self.id().clone())),
+            GameCharacter::Missile(_) => {
+                Some(GameCommand::DestroyEnemy(self.id().clone(), self.points))
+            }
             _ => None,
         }
     }
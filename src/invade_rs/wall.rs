@@ -1,5 +1,6 @@
 use super::character::{GameCharacter, GameCommand, Id};
 use crate::engine::geometry::Rect;
+use crate::engine::random::Rng;
 use crate::engine::DrawCommand;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -36,7 +37,7 @@ impl Wall {
         self.bounding_box.clone()
     }
 
-    pub fn update(&mut self, _delta: f32) -> Option<GameCommand> {
+    pub fn update(&mut self, _delta: f32, _rng: &mut Rng) -> Option<GameCommand> {
         None
     }
 
@@ -48,7 +49,7 @@ impl Wall {
         None
     }
 
-    pub fn draw(&self) -> Option<DrawCommand> {
+    pub fn draw(&self, _alpha: f32) -> Option<DrawCommand> {
         None
     }
 }
@@ -1,10 +1,25 @@
 #[macro_use]
-mod engine;
-mod invade_rs;
+pub mod engine;
+pub mod invade_rs;
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+use futures::StreamExt;
 use wasm_bindgen::prelude::*;
+use web_sys::HtmlElement;
+
+use crate::engine::event::EventSource;
+use crate::engine::replay::{RecordingEventSource, Replay, ReplayEventSource};
+use crate::engine::{
+    audio, browser, event, gamepad, renderer, settings, ui, FixedTimestep, GameLoop,
+};
+use crate::invade_rs::config::{Config, ShieldStyle};
 
-use crate::engine::{event, renderer, GameLoop};
+const TICK_RATE: f32 = 60.0;
+const MAX_CATCH_UP_STEPS: u32 = 5;
+const MAX_FRAME_DELTA: f64 = 100.0;
 
 // When the `wee_alloc` feature is enabled, this uses `wee_alloc` as the global
 // allocator.
@@ -22,14 +37,116 @@ pub fn main_js() -> Result<(), JsValue> {
     #[cfg(debug_assertions)]
     console_error_panic_hook::set_once();
 
-    engine::browser::spawn_local(async move {
-        let game = invade_rs::InvadeRs::new();
+    browser::spawn_local(async move {
+        // Opening the page with `?replay=<url>` plays back a recorded session
+        // instead of taking input.
+        let replay = match browser::query_param("replay") {
+            Ok(Some(url)) => match load_replay(&url).await {
+                Ok(replay) => Some(replay),
+                Err(err) => {
+                    browser::log(&format!("Could not load replay: {:#?}", err));
+                    None
+                }
+            },
+            _ => None,
+        };
+        let seed = replay
+            .as_ref()
+            .and_then(|replay| replay.seed)
+            .unwrap_or_else(rand::random);
+        browser::log(&format!("seed: {}", seed));
+        let config = match &replay {
+            Some(replay) => replay_config(replay).expect("Could not read replay config"),
+            None => page_config(),
+        };
+
+        let mut game = invade_rs::InvadeRs::with_seed(seed)
+            .with_config(config)
+            .with_settings(Rc::new(settings::LocalStorageSettings));
+        match audio::WebAudioBackend::new() {
+            Ok(audio) => game = game.with_audio(Rc::new(audio)),
+            Err(err) => browser::log(&format!("audio disabled: {:#?}", err)),
+        }
         let renderer = renderer::CanvasRenderer::new().expect("Could not create renderer");
-        let event_source = event::BrowserEventSource::new().expect("Could not create event source");
+        if let Some(replay) = replay {
+            start(game, renderer, ReplayEventSource::new(replay)).await;
+            return;
+        }
+
+        let event_source = event::BrowserEventSource::new()
+            .expect("Could not create event source")
+            .chain(gamepad::GamepadEventSource::new(gamepad::BrowserGamepads));
+
+        // Opening the page with `?record` records the session for bug reports.
+        if let Ok(Some(_)) = browser::query_param("record") {
+            let recorder = RecordingEventSource::new(event_source, Some(seed))
+                .with_config(config.to_json().expect("Could not serialize config"));
+            if let Err(err) = show_replay_download(recorder.replay()) {
+                browser::log(&format!("replay download disabled: {:#?}", err));
+            }
+            start(game, renderer, recorder).await;
+        } else {
+            start(game, renderer, event_source).await;
+        }
+    });
+    Ok(())
+}
+
+// Opening the page with `?shields=mask` swaps the tiled shields for ones that
+// crumble pixel by pixel.
+fn page_config() -> Config {
+    match browser::query_param("shields") {
+        Ok(Some(style)) if style == "mask" => Config {
+            shield_style: ShieldStyle::Mask,
+            ..Config::default()
+        },
+        _ => Config::default(),
+    }
+}
+
+async fn load_replay(url: &str) -> Result<Replay> {
+    Replay::from_json(&browser::fetch_text(url).await?)
+}
+
+fn replay_config(replay: &Replay) -> Result<Config> {
+    replay
+        .config
+        .as_deref()
+        .map_or_else(|| Ok(Config::default()), Config::from_json)
+}
+
+async fn start(
+    game: invade_rs::InvadeRs,
+    renderer: renderer::CanvasRenderer,
+    event_source: impl EventSource + 'static,
+) {
+    GameLoop::new(game, renderer, event_source)
+        .with_fixed_timestep(FixedTimestep::new(TICK_RATE, MAX_CATCH_UP_STEPS))
+        .with_max_frame_delta(MAX_FRAME_DELTA)
+        .start()
+        .await
+        .expect("Could not start game loop");
+}
+
+fn show_replay_download(replay: Rc<RefCell<Replay>>) -> Result<()> {
+    ui::draw_ui("<button id=\"download-replay\">REPLAY</button>")?;
+    let button: HtmlElement = browser::document()?
+        .get_element_by_id("download-replay")
+        .ok_or_else(|| anyhow!("Replay button not found"))?
+        .dyn_into()
+        .map_err(|element| anyhow!("Error converting {:#?} to HtmlElement", element))?;
 
-        GameLoop::start(game, renderer, event_source)
-            .await
-            .expect("Could not start game loop");
+    let mut clicks = ui::add_click_handler(button);
+    browser::spawn_local(async move {
+        while clicks.next().await.is_some() {
+            let result = replay
+                .borrow()
+                .to_json()
+                .and_then(|json| browser::download("invade-rs-replay.json", &json));
+            if let Err(err) = result {
+                browser::log(&format!("Could not download replay: {:#?}", err));
+            }
+        }
     });
     Ok(())
 }
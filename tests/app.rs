@@ -8,13 +8,13 @@ wasm_bindgen_test_configure!(run_in_browser);
 // This runs a unit test in native Rust, so it can only use Rust APIs.
 #[test]
 fn rust_test() {
-    assert_eq!(1, 1);
+    assert_eq!(format!("{}", 42), "42");
 }
 
 // This runs a unit test in the browser, so it can use browser APIs.
 #[wasm_bindgen_test]
 fn web_test() {
-    assert_eq!(1, 1);
+    assert_eq!(format!("{}", 42), "42");
 }
 
 // This runs a unit test in the browser, and in addition it supports asynchronous Future APIs.
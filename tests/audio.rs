@@ -0,0 +1,44 @@
+mod common;
+
+use std::rc::Rc;
+
+use futures::executor::block_on;
+
+use invade_rs::engine::audio::{AudioCall, RecordingAudioBackend};
+use invade_rs::engine::event::Event;
+use invade_rs::engine::Game;
+use invade_rs::invade_rs::InvadeRs;
+
+use common::Session;
+
+#[test]
+fn sound_effects_are_synthesized_on_initialize() {
+    let audio = RecordingAudioBackend::new();
+    let mut game = InvadeRs::with_seed(1)
+        .with_sprite_sheet(common::sprite_sheet())
+        .with_audio(Rc::new(audio.clone()));
+
+    block_on(game.initialize()).unwrap();
+
+    let calls = audio.calls();
+    assert_eq!(calls.len(), 12);
+    for call in calls {
+        let AudioCall::Upload(buffer) = call else {
+            panic!("expected only uploads, got {:?}", call);
+        };
+        assert!(0.0 < buffer.duration() && buffer.peak() <= 1.0);
+    }
+}
+
+#[test]
+fn firing_plays_the_shoot_effect() {
+    let mut session = Session::start();
+    let loaded = session.audio.calls().len();
+
+    session.update(&[Event::KeyDown("Space".to_string())]);
+
+    assert!(matches!(
+        session.audio.calls()[loaded..],
+        [AudioCall::Play(_)]
+    ));
+}
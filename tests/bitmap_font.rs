@@ -0,0 +1,46 @@
+mod common;
+
+use std::rc::Rc;
+
+use invade_rs::engine::font::{Align, BitmapFont};
+use invade_rs::engine::geometry::{Point, Rect};
+use invade_rs::engine::renderer::{Renderer, SoftwareRenderer};
+
+fn font() -> BitmapFont {
+    BitmapFont::new(Rc::new(common::sprite_sheet()))
+}
+
+fn lit_columns(renderer: &SoftwareRenderer, y: u32) -> Vec<u32> {
+    let bitmap = renderer.bitmap();
+    (0..bitmap.width())
+        .filter(|x| bitmap.pixel(*x, y).is_some_and(|p| p[..3] != [0, 0, 0]))
+        .collect()
+}
+
+#[test]
+fn measure_includes_spacing_and_scale() {
+    let font = font();
+    assert_eq!(font.measure("AB"), 8 + 2 + 8);
+    assert_eq!(font.measure("A I"), 8 + 2 + 8 + 2 + 6);
+    assert_eq!(font.clone().with_scale(2).measure("AB"), 36);
+    assert_eq!(font.with_spacing(0).measure("ab"), 16);
+}
+
+#[test]
+fn alignment_moves_text_around_anchor() {
+    let font = font();
+    for (align, left, right) in [
+        (Align::Left, 50, 68),
+        (Align::Center, 41, 59),
+        (Align::Right, 32, 50),
+    ] {
+        let renderer = SoftwareRenderer::new(100, 20);
+        renderer.clear(&Rect::new_from_x_y_w_h(0, 0, 100, 20));
+        let command = font.draw("00", &Point { x: 50, y: 0 }, align, 0);
+        command.1(&renderer);
+
+        let columns: Vec<u32> = (0..14).flat_map(|y| lit_columns(&renderer, y)).collect();
+        assert!(columns.iter().all(|x| (left..right).contains(&(*x as i16))));
+        assert!(!columns.is_empty());
+    }
+}
@@ -0,0 +1,284 @@
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::rc::Rc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::executor::block_on;
+
+use invade_rs::engine::audio::{NullAudioBackend, RecordingAudioBackend};
+use invade_rs::engine::event::{Event, EventSource};
+use invade_rs::engine::font::{Align, BitmapFont};
+use invade_rs::engine::geometry::{Point, Rect};
+use invade_rs::engine::image::{Bitmap, Image};
+use invade_rs::engine::input::InputState;
+use invade_rs::engine::renderer::{Renderer, SoftwareRenderer};
+use invade_rs::engine::settings::MemorySettings;
+use invade_rs::engine::sprite::SpriteSheet;
+use invade_rs::engine::{DrawCommand, Game};
+use invade_rs::invade_rs::config::Config;
+use invade_rs::invade_rs::controls::Controls;
+use invade_rs::invade_rs::fsm::{StateMachine, Transition};
+use invade_rs::invade_rs::game_state::in_game::InGame;
+use invade_rs::invade_rs::game_state::out_game::OutGame;
+use invade_rs::invade_rs::game_state::GameStateMachine;
+use invade_rs::invade_rs::progress::Progress;
+use invade_rs::invade_rs::score::Score;
+use invade_rs::invade_rs::sound::SoundBank;
+use invade_rs::invade_rs::InvadeRs;
+
+#[derive(Default)]
+pub struct RecordingGame {
+    pub deltas: Rc<RefCell<Vec<f32>>>,
+    pub events: Rc<RefCell<Vec<Vec<Event>>>>,
+    pub alphas: Rc<RefCell<Vec<f32>>>,
+}
+
+#[async_trait(?Send)]
+impl Game for RecordingGame {
+    async fn initialize(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn update(&mut self, delta: f32, _input: &InputState, events: &[Event]) -> Result<()> {
+        self.deltas.borrow_mut().push(delta);
+        self.events.borrow_mut().push(events.to_vec());
+        Ok(())
+    }
+
+    fn draw(&self, alpha: f32) -> Vec<DrawCommand> {
+        self.alphas.borrow_mut().push(alpha);
+        vec![DrawCommand(
+            0,
+            Box::new(|renderer: &dyn Renderer| {
+                renderer.clear(&Rect::new_from_x_y_w_h(0, 0, 1, 1));
+            }),
+        )]
+    }
+}
+
+#[derive(Default)]
+pub struct CountingRenderer {
+    pub clears: RefCell<usize>,
+}
+
+impl Renderer for CountingRenderer {
+    fn clear(&self, _rect: &Rect) {
+        *self.clears.borrow_mut() += 1;
+    }
+
+    fn draw_image(&self, _image: &Image, _frame: &Rect, _destination: &Rect) {}
+
+    fn draw_rect(&self, _rect: &Rect) {}
+
+    fn fill_rect(&self, _rect: &Rect, _color: [u8; 4]) {}
+}
+
+pub type Draw = ((i16, i16, i16, i16), (i16, i16, i16, i16));
+
+// Logs the source and destination of every sprite drawn.
+#[derive(Default)]
+pub struct DrawLog(pub RefCell<Vec<Draw>>);
+
+impl DrawLog {
+    pub fn of(draw_commands: &[DrawCommand]) -> Self {
+        let log = Self::default();
+        draw_commands.iter().for_each(|d| d.1(&log));
+        log
+    }
+
+    // Whether every sprite in `other` was drawn here too, in the same place.
+    pub fn contains(&self, other: &DrawLog) -> bool {
+        let drawn = self.0.borrow();
+        other.0.borrow().iter().all(|draw| drawn.contains(draw))
+    }
+}
+
+impl Renderer for DrawLog {
+    fn clear(&self, _rect: &Rect) {}
+
+    fn draw_image(&self, _image: &Image, frame: &Rect, destination: &Rect) {
+        let rect = |r: &Rect| (r.x(), r.y(), r.width(), r.height());
+        self.0.borrow_mut().push((rect(frame), rect(destination)));
+    }
+
+    fn draw_rect(&self, _rect: &Rect) {}
+
+    fn fill_rect(&self, _rect: &Rect, _color: [u8; 4]) {}
+}
+
+#[derive(Default)]
+pub struct QueuedEvents(pub Rc<RefCell<VecDeque<Event>>>);
+
+impl EventSource for QueuedEvents {
+    fn try_next(&mut self) -> Option<Event> {
+        self.0.borrow_mut().pop_front()
+    }
+}
+
+// Feeds a game its events the way `GameLoop` does, keeping held keys in sync.
+#[derive(Default)]
+pub struct InputDriver {
+    pub input: InputState,
+}
+
+impl InputDriver {
+    pub fn update(&mut self, game: &mut impl Game, delta: f32, events: &[Event]) -> Result<()> {
+        self.input.update(events);
+        game.update(delta, &self.input, events)
+    }
+}
+
+pub fn sprite_sheet() -> SpriteSheet {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    SpriteSheet::load_from_files(
+        root.join("static/texture.json").to_str().unwrap(),
+        root.join("static/texture.png").to_str().unwrap(),
+    )
+    .unwrap()
+}
+
+pub const FRAME: f32 = 1000.0 / 60.0;
+
+// A seeded game played frame by frame, with every sound it asks for logged.
+pub struct Session {
+    pub game: InvadeRs,
+    pub audio: RecordingAudioBackend,
+    pub driver: InputDriver,
+}
+
+impl Session {
+    pub fn start() -> Self {
+        Self::start_with(|game| game)
+    }
+
+    // `setup` adds options to the game before it is initialized. The first
+    // frame fires past the title, the next two enter the game and let the
+    // march sound its first note.
+    pub fn start_with(setup: impl FnOnce(InvadeRs) -> InvadeRs) -> Self {
+        let mut session = Self::title_with(setup);
+        session.tap(&["Space"]);
+        session.run(2);
+        session
+    }
+
+    // The game as it first loads, waiting on the title.
+    pub fn title_with(setup: impl FnOnce(InvadeRs) -> InvadeRs) -> Self {
+        let audio = RecordingAudioBackend::new();
+        let game = InvadeRs::with_seed(1)
+            .with_sprite_sheet(sprite_sheet())
+            .with_audio(Rc::new(audio.clone()));
+        let mut game = setup(game);
+        block_on(game.initialize()).unwrap();
+
+        Self {
+            game,
+            audio,
+            driver: InputDriver::default(),
+        }
+    }
+
+    pub fn update(&mut self, events: &[Event]) {
+        self.driver.update(&mut self.game, FRAME, events).unwrap();
+    }
+
+    pub fn run(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.update(&[]);
+        }
+    }
+
+    // Presses and releases each key within one frame.
+    pub fn tap(&mut self, codes: &[&str]) {
+        let events: Vec<Event> = codes
+            .iter()
+            .flat_map(|code| {
+                [
+                    Event::KeyDown(code.to_string()),
+                    Event::KeyUp(code.to_string()),
+                ]
+            })
+            .collect();
+        self.update(&events);
+    }
+
+    pub fn render(&self) -> Bitmap {
+        let renderer = SoftwareRenderer::new(600, 600);
+        let mut draw_commands = self.game.draw(1.0);
+        draw_commands.sort_by_key(|d| d.0);
+        draw_commands.iter().for_each(|d| d.1(&renderer));
+        let bitmap = renderer.bitmap().clone();
+        bitmap
+    }
+
+    // Columns covered by the orange ship sprite on its row of the screen.
+    pub fn ship_columns(&self) -> Vec<u32> {
+        const SHIP_ROW: u32 = 575;
+
+        let bitmap = self.render();
+        (0..600)
+            .filter(|x| {
+                let [r, g, b, _] = bitmap.pixel(*x, SHIP_ROW).unwrap();
+                200 < r && 80 < g && g < 200 && b < 80
+            })
+            .collect()
+    }
+
+    // Whether the title's prompt to start and to open the controls is drawn.
+    pub fn shows_title(&self) -> bool {
+        let sprite_sheet = Rc::new(sprite_sheet());
+        let banner = BitmapFont::new(sprite_sheet.clone()).with_scale(2);
+        let hint = BitmapFont::new(sprite_sheet);
+        let prompt = DrawLog::of(&[
+            banner.draw("PRESS FIRE", &Point { x: 300, y: 400 }, Align::Center, 0),
+            hint.draw("C CONTROLS", &Point { x: 300, y: 448 }, Align::Center, 0),
+        ]);
+        DrawLog::of(&self.game.draw(1.0)).contains(&prompt)
+    }
+
+    pub fn ship_x(&self) -> u32 {
+        let xs = self.ship_columns();
+        (xs[0] + xs[xs.len() - 1]) / 2
+    }
+}
+
+const MAX_STATE_FRAMES: usize = 1000;
+
+// Sets up the start of a wave the way the game does between waves.
+pub fn wave(wave: u32, config: Config) -> OutGame {
+    let progress = Progress {
+        wave,
+        ..Progress::new(&config, Score::default())
+    };
+    OutGame::new(
+        Rc::new(sprite_sheet()),
+        Rc::new(SoundBank::new(Rc::new(NullAudioBackend::default()))),
+        Rc::new(Controls::new(Rc::new(MemorySettings::new()))),
+        config,
+        1,
+        progress,
+    )
+}
+
+// Updates the state until it hands over to another one, returning that and
+// how many frames it took.
+pub fn run_until_replaced(mut state: GameStateMachine) -> (GameStateMachine, usize) {
+    for frame in 1..=MAX_STATE_FRAMES {
+        match state.update(FRAME, &InputState::new(), &[]) {
+            Transition::Stay(next) => state = next,
+            Transition::Replace(next) => return (next, frame),
+            _ => panic!("unexpected transition"),
+        }
+    }
+    panic!("still running after {} frames", MAX_STATE_FRAMES);
+}
+
+pub fn play(out_game: OutGame) -> InGame {
+    match run_until_replaced(GameStateMachine::OutGame(out_game)) {
+        (GameStateMachine::InGame(in_game), _) => in_game,
+        _ => panic!("the wave did not start"),
+    }
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use std::rc::Rc;
+
+use invade_rs::engine::audio::AudioCall;
+use invade_rs::engine::event::Event;
+use invade_rs::engine::gamepad::GamepadButton;
+use invade_rs::engine::settings::{MemorySettings, SettingsStore};
+
+use common::Session;
+
+fn start(settings: &MemorySettings) -> Session {
+    Session::start_with(|game| game.with_settings(Rc::new(settings.clone())))
+}
+
+// Whether pressing and then releasing `code` plays a sound.
+fn fires(session: &mut Session, code: &str) -> bool {
+    let before = session.audio.calls().len();
+    session.update(&[key_down(code)]);
+    session.update(&[Event::KeyUp(code.to_string())]);
+    session.audio.calls()[before..]
+        .iter()
+        .any(|call| matches!(call, AudioCall::Play(_)))
+}
+
+fn key_down(code: &str) -> Event {
+    Event::KeyDown(code.to_string())
+}
+
+#[test]
+fn alternative_keys_fire() {
+    let mut session = start(&MemorySettings::new());
+
+    assert!(fires(&mut session, "KeyZ"));
+}
+
+#[test]
+fn gamepad_buttons_fire() {
+    let mut session = start(&MemorySettings::new());
+    let before = session.audio.calls().len();
+
+    session.update(&[Event::GamepadButtonDown(0, GamepadButton::South)]);
+
+    assert!(matches!(
+        session.audio.calls()[before..],
+        [AudioCall::Play(_)]
+    ));
+}
+
+#[test]
+fn holding_fire_shoots_once() {
+    let mut session = start(&MemorySettings::new());
+    session.update(&[key_down("Space")]);
+    let before = session.audio.calls().len();
+
+    // Auto-repeat and a second fire key while the first is held.
+    session.update(&[key_down("Space"), key_down("KeyZ")]);
+    session.update(&[key_down("Space")]);
+
+    assert_eq!(session.audio.calls().len(), before);
+}
+
+#[test]
+fn rebinding_is_saved_and_restored() {
+    let settings = MemorySettings::new();
+    let mut session = start(&settings);
+    assert!(!fires(&mut session, "KeyF"));
+
+    // Controls is the second entry of the pause menu; Fire is its third row.
+    session.tap(&["Escape"]);
+    session.tap(&["ArrowDown", "Enter"]);
+    session.tap(&["ArrowDown", "ArrowDown", "Enter"]);
+    session.tap(&["KeyF"]);
+    assert!(settings.load("invade-rs.bindings").unwrap().is_some());
+
+    let mut session = start(&settings);
+    assert!(fires(&mut session, "KeyF"));
+}
+
+#[test]
+fn losing_focus_pauses_the_game() {
+    let mut session = start(&MemorySettings::new());
+
+    session.update(&[Event::FocusLost]);
+    assert!(!fires(&mut session, "Space"));
+
+    session.update(&[Event::FocusGained]);
+    session.tap(&["Escape"]);
+    assert!(fires(&mut session, "Space"));
+}
+
+#[test]
+fn clearing_pause_keeps_a_way_to_pause() {
+    let settings = MemorySettings::new();
+    let mut session = start(&settings);
+
+    // Pause is the fourth row of the controls screen.
+    session.tap(&["Escape"]);
+    session.tap(&["ArrowDown", "Enter"]);
+    session.tap(&["ArrowDown", "ArrowDown", "ArrowDown", "Backspace"]);
+    session.tap(&["Escape"]);
+    session.tap(&["Escape"]);
+    assert!(fires(&mut session, "Space"));
+
+    session.tap(&["KeyP"]);
+    assert!(!fires(&mut session, "Space"));
+}
+
+#[test]
+fn controls_open_from_the_title() {
+    let mut session = start(&MemorySettings::new());
+    session.tap(&["Escape"]);
+    session.tap(&["ArrowUp", "Enter"]);
+
+    session.tap(&["KeyC"]);
+    session.tap(&["ArrowDown", "ArrowDown", "Enter"]);
+    session.tap(&["KeyF"]);
+    session.tap(&["Escape"]);
+
+    session.tap(&["KeyF"]);
+    session.update(&[]);
+    assert!(fires(&mut session, "KeyF"));
+}
+
+#[test]
+fn the_game_opens_on_the_title_with_the_controls_at_hand() {
+    let settings = MemorySettings::new();
+    let mut session = Session::title_with(|game| game.with_settings(Rc::new(settings.clone())));
+    session.run(60);
+    assert!(session.shows_title());
+
+    session.tap(&["KeyC"]);
+    session.tap(&["ArrowDown", "ArrowDown", "Enter"]);
+    session.tap(&["KeyF"]);
+    session.tap(&["Escape"]);
+    assert!(session.shows_title());
+
+    session.tap(&["KeyF"]);
+    session.update(&[]);
+    assert!(!session.shows_title());
+    assert!(fires(&mut session, "KeyF"));
+}
+
+#[test]
+fn last_pressed_direction_wins_and_releasing_it_resumes_the_other() {
+    let mut session = start(&MemorySettings::new());
+    session.update(&[key_down("ArrowRight")]);
+    session.update(&[key_down("ArrowRight")]);
+    let start = session.ship_x();
+
+    session.update(&[key_down("ArrowLeft")]);
+    session.update(&[]);
+    let left = session.ship_x();
+    assert!(left < start);
+
+    session.update(&[Event::KeyUp("ArrowLeft".to_string())]);
+    session.update(&[]);
+    assert!(left < session.ship_x());
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use invade_rs::engine::event::Event;
+use invade_rs::engine::geometry::{Point, Rect};
+use invade_rs::engine::image::Image;
+use invade_rs::engine::input::InputState;
+use invade_rs::engine::random::Rng;
+use invade_rs::engine::renderer::Renderer;
+use invade_rs::engine::Game;
+use invade_rs::invade_rs::character::{GameCharacter, GameCommand};
+use invade_rs::invade_rs::config::Config;
+use invade_rs::invade_rs::explosion::Explosion;
+use invade_rs::invade_rs::fsm::{StateMachine, Transition};
+use invade_rs::invade_rs::game_state::GameStateMachine;
+use invade_rs::invade_rs::sound::SoundEffect;
+use invade_rs::invade_rs::InvadeRs;
+
+use common::{play, wave, DrawLog, Session, FRAME};
+
+// Counts the sprites drawn from the explosion cells of the sheet.
+struct ExplosionCounter {
+    frames: Vec<(i16, i16)>,
+    count: RefCell<usize>,
+}
+
+impl ExplosionCounter {
+    fn new() -> Self {
+        let json = include_str!("../static/texture.json");
+        let sheet: serde_json::Value = serde_json::from_str(json).unwrap();
+        let frames = ["explode_0.png", "explode_1.png"]
+            .iter()
+            .map(|name| {
+                let frame = &sheet["frames"][name]["frame"];
+                let coordinate = |key: &str| frame[key].as_i64().unwrap() as i16;
+                (coordinate("x"), coordinate("y"))
+            })
+            .collect();
+        Self {
+            frames,
+            count: RefCell::new(0),
+        }
+    }
+}
+
+impl Renderer for ExplosionCounter {
+    fn clear(&self, _rect: &Rect) {}
+
+    fn draw_image(&self, _image: &Image, frame: &Rect, _destination: &Rect) {
+        if self.frames.contains(&(frame.x(), frame.y())) {
+            *self.count.borrow_mut() += 1;
+        }
+    }
+
+    fn draw_rect(&self, _rect: &Rect) {}
+
+    fn fill_rect(&self, _rect: &Rect, _color: [u8; 4]) {}
+}
+
+fn explosions(game: &InvadeRs) -> usize {
+    let renderer = ExplosionCounter::new();
+    game.draw(1.0).iter().for_each(|d| d.1(&renderer));
+    let count = *renderer.count.borrow();
+    count
+}
+
+#[test]
+fn shooting_an_enemy_blows_up_both_it_and_the_missile() {
+    let mut session = Session::start();
+    session.update(&[Event::KeyDown("Space".to_string())]);
+    session.update(&[Event::KeyUp("Space".to_string())]);
+
+    // The missile reaches the fleet well within five seconds.
+    let counts: Vec<usize> = (0..300)
+        .map(|_| {
+            session.update(&[]);
+            explosions(&session.game)
+        })
+        .collect();
+    assert_eq!(counts.iter().max(), Some(&2));
+}
+
+// Where explosions are drawn in the state's next frame.
+fn explosions_drawn(state: &GameStateMachine) -> Vec<Rect> {
+    let frames = ExplosionCounter::new().frames;
+    let log = DrawLog::of(&state.draw(1.0));
+    let drawn = log.0.borrow();
+    drawn
+        .iter()
+        .filter(|(source, _)| frames.contains(&(source.0, source.1)))
+        .map(|(_, (x, y, width, height))| Rect::new_from_x_y_w_h(*x, *y, *width, *height))
+        .collect()
+}
+
+// The first wave without shields, and with only the leftmost Ferris left
+// when `lone` is set, so that shots fly past everything and off the screen.
+fn without_shields(lone: bool) -> GameStateMachine {
+    let mut in_game = play(wave(1, Config::default()));
+    let leftmost = in_game
+        .characters
+        .iter()
+        .filter(|c| matches!(&*c.borrow(), GameCharacter::Ferris(_)))
+        .min_by_key(|c| c.borrow().bounding_box().x())
+        .map(|c| c.borrow().id().clone())
+        .unwrap();
+    in_game.characters.retain(|c| {
+        let c = c.borrow();
+        match &*c {
+            GameCharacter::Ferris(_) => !lone || c.id() == &leftmost,
+            _ => !c.is_shield(),
+        }
+    });
+    GameStateMachine::InGame(in_game)
+}
+
+// Plays until an explosion is drawn centered on the screen row `y`.
+fn bursts_on_row(mut state: GameStateMachine, events: &[Event], y: i16) -> bool {
+    let mut input = InputState::new();
+    let mut events = events.to_vec();
+    for _ in 0..600 {
+        input.update(&events);
+        state = match state.update(FRAME, &input, &events) {
+            Transition::Stay(next) => next,
+            _ => panic!("unexpected transition"),
+        };
+        events.clear();
+        if explosions_drawn(&state)
+            .iter()
+            .any(|explosion| explosion.center().y == y)
+        {
+            return true;
+        }
+    }
+    false
+}
+
+#[test]
+fn a_missile_that_misses_bursts_at_the_top_of_the_screen() {
+    let fire = [
+        Event::KeyDown("Space".to_string()),
+        Event::KeyUp("Space".to_string()),
+    ];
+    assert!(bursts_on_row(without_shields(true), &fire, 0));
+}
+
+#[test]
+fn a_beam_that_misses_bursts_at_the_bottom_of_the_screen() {
+    assert!(bursts_on_row(without_shields(false), &[], 599));
+}
+
+#[test]
+fn an_explosion_sounds_its_blast_once_as_it_starts() {
+    let sprite_sheet = Rc::new(common::sprite_sheet());
+    let mut explosion = Explosion::new(sprite_sheet, Point::default(), SoundEffect::EnemyDeath);
+    let mut rng = Rng::new(1);
+
+    assert!(matches!(
+        explosion.update(FRAME, &mut rng),
+        Some(GameCommand::PlaySound(SoundEffect::EnemyDeath))
+    ));
+    for _ in 0..60 {
+        match explosion.update(FRAME, &mut rng) {
+            None => {}
+            Some(GameCommand::DestroyCharacter(_)) => return,
+            Some(_) => panic!("the explosion sounded again"),
+        }
+    }
+    panic!("the explosion never finished");
+}
@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use invade_rs::engine::input::InputState;
+use invade_rs::invade_rs::fsm::{StateMachine, StateMachineRunner, Transition};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+// Each state is a name; its next update returns whatever transition is
+// queued on it, and stays otherwise.
+struct Named {
+    name: &'static str,
+    log: Log,
+    queued: RefCell<Option<Box<Transition<Named>>>>,
+}
+
+impl Named {
+    fn new(name: &'static str, log: &Log) -> Self {
+        Self {
+            name,
+            log: log.clone(),
+            queued: RefCell::new(None),
+        }
+    }
+
+    fn then(self, transition: Transition<Named>) -> Self {
+        *self.queued.borrow_mut() = Some(Box::new(transition));
+        self
+    }
+
+    fn record(&self, hook: &str) -> Result<()> {
+        self.log
+            .borrow_mut()
+            .push(format!("{} {}", hook, self.name));
+        Ok(())
+    }
+}
+
+impl Default for Named {
+    fn default() -> Self {
+        Self::new("root", &Log::default())
+    }
+}
+
+impl StateMachine<()> for Named {
+    fn update(&self, _delta: f32, _input: &InputState, _events: &[()]) -> Transition<Self> {
+        self.queued
+            .borrow_mut()
+            .take()
+            .map(|transition| *transition)
+            .unwrap_or_else(|| Transition::Stay(Named::new(self.name, &self.log)))
+    }
+
+    fn on_enter(&mut self) -> Result<()> {
+        self.record("enter")
+    }
+
+    fn on_exit(&mut self) -> Result<()> {
+        self.record("exit")
+    }
+
+    fn on_pause(&mut self) -> Result<()> {
+        self.record("pause")
+    }
+
+    fn on_resume(&mut self) -> Result<()> {
+        self.record("resume")
+    }
+}
+
+fn names(runner: &StateMachineRunner<(), Named>) -> Vec<&'static str> {
+    runner.states().map(|state| state.name).collect()
+}
+
+#[test]
+fn push_pauses_and_pop_resumes_the_state_underneath() {
+    let log = Log::default();
+    let mut runner = StateMachineRunner::new();
+    runner.replace(Named::new("game", &log)).unwrap();
+
+    runner.push(Named::new("menu", &log)).unwrap();
+    assert_eq!(names(&runner), vec!["game", "menu"]);
+    runner.update(16.0, &InputState::new(), &[]).unwrap();
+    assert_eq!(runner.state().name, "menu");
+
+    runner.pop().unwrap();
+    assert_eq!(names(&runner), vec!["game"]);
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "enter game",
+            "pause game",
+            "enter menu",
+            "exit menu",
+            "resume game"
+        ]
+    );
+}
+
+#[test]
+fn reset_exits_every_state() {
+    let log = Log::default();
+    let mut runner = StateMachineRunner::new();
+    runner.replace(Named::new("game", &log)).unwrap();
+    runner.push(Named::new("menu", &log)).unwrap();
+    log.borrow_mut().clear();
+
+    runner
+        .apply(Transition::Reset(Named::new("title", &log)))
+        .unwrap();
+
+    assert_eq!(names(&runner), vec!["title"]);
+    assert_eq!(*log.borrow(), vec!["exit menu", "exit game", "enter title"]);
+}
+
+#[test]
+fn update_replaces_the_top_state_with_the_queued_one() {
+    let log = Log::default();
+    let mut runner = StateMachineRunner::new();
+    runner.replace(Named::new("game", &log)).unwrap();
+    let next_wave = Named::new("next wave", &log);
+    runner
+        .push(Named::new("banner", &log).then(Transition::Replace(next_wave)))
+        .unwrap();
+    log.borrow_mut().clear();
+
+    runner.update(16.0, &InputState::new(), &[]).unwrap();
+    assert_eq!(names(&runner), vec!["game", "next wave"]);
+    runner.update(16.0, &InputState::new(), &[]).unwrap();
+    assert_eq!(names(&runner), vec!["game", "next wave"]);
+
+    assert_eq!(*log.borrow(), vec!["exit banner", "enter next wave"]);
+}
+
+#[test]
+fn the_last_state_cannot_be_popped() {
+    let mut runner: StateMachineRunner<(), Named> = StateMachineRunner::new();
+
+    assert!(runner.pop().is_err());
+    assert_eq!(names(&runner), vec!["root"]);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use futures::executor::block_on;
+
+use invade_rs::engine::clock::ManualClock;
+use invade_rs::engine::event::Event;
+use invade_rs::engine::scheduler::ManualScheduler;
+use invade_rs::engine::{FixedTimestep, GameLoop};
+
+use common::{CountingRenderer, QueuedEvents, RecordingGame};
+
+#[test]
+fn step_passes_explicit_timestamps_as_deltas() {
+    let game = RecordingGame::default();
+    let deltas = game.deltas.clone();
+    let mut game_loop = GameLoop::new(game, CountingRenderer::default(), QueuedEvents::default());
+
+    block_on(game_loop.initialize(&ManualClock::new(100.0))).unwrap();
+    game_loop.step(116.0).unwrap();
+    game_loop.step(150.0).unwrap();
+
+    assert_eq!(*deltas.borrow(), vec![16.0, 34.0]);
+    assert_eq!(*game_loop.renderer().clears.borrow(), 2);
+}
+
+#[test]
+fn manual_scheduler_drives_the_loop() {
+    let game = RecordingGame::default();
+    let deltas = game.deltas.clone();
+    let recorded = game.events.clone();
+    let events = QueuedEvents::default();
+    let queue = events.0.clone();
+
+    let clock = ManualClock::new(0.0);
+    let scheduler = ManualScheduler::new(clock.clone());
+    block_on(GameLoop::new(game, CountingRenderer::default(), events).run(&clock, &scheduler))
+        .unwrap();
+
+    scheduler.advance(10.0).unwrap();
+    queue
+        .borrow_mut()
+        .push_back(Event::KeyDown("Space".to_string()));
+    scheduler.advance(20.0).unwrap();
+
+    assert_eq!(*deltas.borrow(), vec![10.0, 20.0]);
+    assert_eq!(
+        *recorded.borrow(),
+        vec![vec![], vec![Event::KeyDown("Space".to_string())]]
+    );
+}
+
+#[test]
+fn fixed_timestep_accumulates_frame_time_into_ticks() {
+    let game = RecordingGame::default();
+    let deltas = game.deltas.clone();
+    let alphas = game.alphas.clone();
+    let mut game_loop = GameLoop::new(game, CountingRenderer::default(), QueuedEvents::default())
+        .with_fixed_timestep(FixedTimestep::new(100.0, 5));
+
+    block_on(game_loop.initialize(&ManualClock::new(0.0))).unwrap();
+    game_loop.step(5.0).unwrap();
+    game_loop.step(25.0).unwrap();
+
+    assert_eq!(*deltas.borrow(), vec![10.0, 10.0]);
+    assert_eq!(*alphas.borrow(), vec![0.5, 0.5]);
+}
+
+#[test]
+fn fixed_timestep_drops_backlog_beyond_max_steps() {
+    let game = RecordingGame::default();
+    let deltas = game.deltas.clone();
+    let alphas = game.alphas.clone();
+    let mut game_loop = GameLoop::new(game, CountingRenderer::default(), QueuedEvents::default())
+        .with_fixed_timestep(FixedTimestep::new(100.0, 3));
+
+    block_on(game_loop.initialize(&ManualClock::new(0.0))).unwrap();
+    game_loop.step(5004.0).unwrap();
+    game_loop.step(5010.0).unwrap();
+
+    assert_eq!(deltas.borrow().len(), 4);
+    assert!((alphas.borrow()[0] - 0.4).abs() < 1e-4);
+}
+
+#[test]
+fn max_frame_delta_clamps_long_frames() {
+    let game = RecordingGame::default();
+    let deltas = game.deltas.clone();
+    let mut game_loop = GameLoop::new(game, CountingRenderer::default(), QueuedEvents::default())
+        .with_max_frame_delta(100.0);
+
+    block_on(game_loop.initialize(&ManualClock::new(0.0))).unwrap();
+    game_loop.step(16.0).unwrap();
+    game_loop.step(3016.0).unwrap();
+
+    assert_eq!(*deltas.borrow(), vec![16.0, 100.0]);
+}
@@ -0,0 +1,83 @@
+use invade_rs::engine::event::{Event, EventSource};
+use invade_rs::engine::gamepad::{
+    GamepadButton, GamepadEventSource, GamepadSnapshot, ManualGamepads,
+};
+
+fn pad(index: u32, pressed: &[usize], axes: [f64; 2]) -> GamepadSnapshot {
+    let mut buttons = vec![false; 17];
+    for i in pressed {
+        buttons[*i] = true;
+    }
+    GamepadSnapshot {
+        index,
+        buttons,
+        axes: axes.to_vec(),
+    }
+}
+
+fn poll(source: &mut GamepadEventSource<ManualGamepads>, frame: u64) -> Vec<Event> {
+    source.begin_update(frame, 0.0);
+    let mut events = vec![];
+    while let Some(event) = source.try_next() {
+        events.push(event);
+    }
+    events
+}
+
+#[test]
+fn buttons_report_changes_only() {
+    let pads = ManualGamepads::new();
+    let mut source = GamepadEventSource::new(pads.clone());
+
+    pads.set(vec![pad(0, &[0], [0.0, 0.0])]);
+    assert_eq!(
+        poll(&mut source, 0),
+        vec![
+            Event::GamepadConnected(0),
+            Event::GamepadButtonDown(0, GamepadButton::South)
+        ]
+    );
+    assert_eq!(poll(&mut source, 1), vec![]);
+
+    pads.set(vec![pad(0, &[14], [0.0, 0.0])]);
+    assert_eq!(
+        poll(&mut source, 2),
+        vec![
+            Event::GamepadButtonUp(0, GamepadButton::South),
+            Event::GamepadButtonDown(0, GamepadButton::DPadLeft)
+        ]
+    );
+}
+
+#[test]
+fn stick_inside_the_dead_zone_is_ignored() {
+    let pads = ManualGamepads::new();
+    let mut source = GamepadEventSource::new(pads.clone()).with_dead_zone(0.3);
+
+    pads.set(vec![pad(0, &[], [0.2, -0.25])]);
+    assert_eq!(poll(&mut source, 0), vec![Event::GamepadConnected(0)]);
+
+    pads.set(vec![pad(0, &[], [0.8, -0.25])]);
+    assert_eq!(
+        poll(&mut source, 1),
+        vec![Event::GamepadButtonDown(0, GamepadButton::StickRight)]
+    );
+}
+
+#[test]
+fn disconnecting_releases_held_buttons() {
+    let pads = ManualGamepads::new();
+    let mut source = GamepadEventSource::new(pads.clone());
+    pads.set(vec![pad(0, &[9], [0.0, 0.0]), pad(1, &[], [0.0, 0.0])]);
+    poll(&mut source, 0);
+
+    pads.set(vec![pad(1, &[], [0.0, 0.0])]);
+
+    assert_eq!(
+        poll(&mut source, 1),
+        vec![
+            Event::GamepadButtonUp(0, GamepadButton::Start),
+            Event::GamepadDisconnected(0)
+        ]
+    );
+}
@@ -0,0 +1,137 @@
+use invade_rs::engine::event::Event;
+use invade_rs::engine::gamepad::GamepadButton;
+use invade_rs::engine::input::{ActionEvent, InputState, KeyBindings};
+use invade_rs::invade_rs::controls::{default_bindings, key_label, Action};
+
+#[test]
+fn actions_accept_several_keys() {
+    let bindings = default_bindings();
+
+    assert_eq!(
+        bindings.translate(&Event::KeyDown("KeyA".to_string())),
+        Some(ActionEvent::Pressed(Action::MoveLeft))
+    );
+    assert_eq!(
+        bindings.translate(&Event::KeyUp("ArrowLeft".to_string())),
+        Some(ActionEvent::Released(Action::MoveLeft))
+    );
+    assert_eq!(
+        bindings.translate(&Event::KeyDown("KeyQ".to_string())),
+        None
+    );
+}
+
+#[test]
+fn binding_a_key_moves_it_between_actions() {
+    let mut bindings = default_bindings();
+
+    bindings.bind(Action::Fire, "KeyA");
+
+    assert_eq!(bindings.action("KeyA"), Some(Action::Fire));
+    assert!(!bindings
+        .codes(Action::MoveLeft)
+        .contains(&"KeyA".to_string()));
+    assert!(bindings
+        .codes(Action::MoveLeft)
+        .contains(&"ArrowLeft".to_string()));
+}
+
+#[test]
+fn bindings_round_trip_through_json() {
+    let mut bindings = default_bindings();
+    bindings.clear(Action::Pause);
+    bindings.bind(Action::MoveLeft, "KeyQ");
+
+    let restored = KeyBindings::from_json(&bindings.to_json().unwrap()).unwrap();
+
+    assert_eq!(restored, bindings);
+    assert!(KeyBindings::<Action>::from_json("{").is_err());
+}
+
+#[test]
+fn key_labels_fit_the_bitmap_font() {
+    assert_eq!(key_label("KeyW"), "W");
+    assert_eq!(key_label("Digit7"), "7");
+    assert_eq!(key_label("ArrowLeft"), "LEFT");
+    assert_eq!(key_label("Escape"), "ESC");
+    assert_eq!(key_label("Semicolon"), "SEMICOLON");
+    assert_eq!(key_label("Numpad+"), "NUMPAD");
+}
+
+#[test]
+fn input_state_ignores_auto_repeat() {
+    let mut input = InputState::new();
+
+    input.update(&[Event::KeyDown("Space".to_string())]);
+    assert!(input.was_pressed("Space") && input.is_held("Space"));
+
+    input.update(&[Event::KeyDown("Space".to_string())]);
+    assert!(!input.was_pressed("Space") && input.is_held("Space"));
+
+    input.update(&[Event::KeyUp("Space".to_string())]);
+    assert!(input.was_released("Space") && !input.is_held("Space"));
+}
+
+#[test]
+fn action_stays_held_while_any_of_its_keys_is_down() {
+    let bindings = default_bindings();
+    let mut input = InputState::new();
+
+    input.update(&[
+        Event::KeyDown("ArrowRight".to_string()),
+        Event::KeyDown("KeyD".to_string()),
+    ]);
+    assert!(input.was_action_pressed(&bindings, Action::MoveRight));
+
+    input.update(&[Event::KeyUp("KeyD".to_string())]);
+    assert!(input.is_action_held(&bindings, Action::MoveRight));
+    assert!(!input.was_action_released(&bindings, Action::MoveRight));
+
+    input.update(&[Event::KeyUp("ArrowRight".to_string())]);
+    assert!(input.was_action_released(&bindings, Action::MoveRight));
+}
+
+#[test]
+fn tapping_within_a_frame_is_a_press_and_a_release() {
+    let bindings = default_bindings();
+    let mut input = InputState::new();
+
+    input.update(&[
+        Event::KeyDown("Space".to_string()),
+        Event::KeyUp("Space".to_string()),
+    ]);
+
+    assert!(input.was_action_pressed(&bindings, Action::Fire));
+    assert!(input.was_action_released(&bindings, Action::Fire));
+    assert!(!input.is_action_held(&bindings, Action::Fire));
+}
+
+#[test]
+fn a_button_stays_held_while_any_pad_holds_it() {
+    let mut input = InputState::new();
+    input.update(&[
+        Event::GamepadButtonDown(0, GamepadButton::South),
+        Event::GamepadButtonDown(1, GamepadButton::South),
+    ]);
+
+    input.update(&[Event::GamepadButtonUp(1, GamepadButton::South)]);
+    assert!(input.is_held("PadSouth"));
+    assert!(!input.was_released("PadSouth"));
+
+    input.update(&[Event::GamepadButtonUp(0, GamepadButton::South)]);
+    assert!(input.was_released("PadSouth") && !input.is_held("PadSouth"));
+}
+
+#[test]
+fn losing_focus_releases_every_held_key() {
+    let mut input = InputState::new();
+    input.update(&[
+        Event::KeyDown("ArrowLeft".to_string()),
+        Event::KeyDown("Space".to_string()),
+    ]);
+
+    input.update(&[Event::FocusLost]);
+
+    assert!(!input.is_held("ArrowLeft"));
+    assert!(input.was_released("Space"));
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use invade_rs::invade_rs::config::Config;
+
+use common::{Session, FRAME};
+
+const MAX_FRAMES: usize = 10000;
+
+fn start(lives: u32) -> Session {
+    Session::start_with(|game| {
+        game.with_config(Config {
+            lives,
+            ..Config::default()
+        })
+    })
+}
+
+// Runs the game with the ship standing still until `done` holds, returning
+// how many frames that took.
+fn run_until(session: &mut Session, done: impl Fn(&Session) -> bool) -> usize {
+    for frame in 0..MAX_FRAMES {
+        session.update(&[]);
+        if done(session) {
+            return frame;
+        }
+    }
+    panic!("gave up after {} frames", MAX_FRAMES);
+}
+
+fn ship_visible(session: &Session) -> bool {
+    !session.ship_columns().is_empty()
+}
+
+// Whether the ship was drawn on each of the next `ms` milliseconds of frames.
+fn visibility(session: &mut Session, ms: f32) -> Vec<bool> {
+    (0..(ms / FRAME) as usize)
+        .map(|_| {
+            session.update(&[]);
+            ship_visible(session)
+        })
+        .collect()
+}
+
+#[test]
+fn a_hit_costs_one_life_and_respawns_the_ship_blinking() {
+    let mut session = start(3);
+
+    run_until(&mut session, |session| session.game.lives() != Some(3));
+    assert_eq!(session.game.lives(), Some(2));
+
+    let exploding = visibility(&mut session, 2000.0);
+    assert!(exploding.iter().all(|visible| !visible));
+
+    run_until(&mut session, ship_visible);
+    let respawned = visibility(&mut session, 2500.0);
+    let (invulnerable, recovered) = respawned.split_at(respawned.len() - 30);
+    assert!(invulnerable.contains(&false));
+    assert!(recovered.iter().all(|visible| *visible));
+    assert_eq!(session.game.lives(), Some(2));
+}
+
+#[test]
+fn losing_the_last_life_returns_to_the_title_once_the_ship_has_exploded() {
+    let mut session = start(2);
+
+    run_until(&mut session, |session| session.game.lives() == Some(0));
+    assert_eq!(session.game.seed(), Some(1));
+
+    let frames = run_until(&mut session, |session| session.game.lives() != Some(0));
+    assert!(1000.0 < frames as f32 * FRAME);
+    assert_eq!(session.game.lives(), Some(2));
+    assert_ne!(session.game.seed(), Some(1));
+    assert!(session.shows_title());
+}
+
+#[test]
+fn fleet_reaching_the_ship_row_ends_the_game_at_once() {
+    let mut session = start(50);
+
+    run_until(&mut session, |session| session.game.lives() < Some(48));
+    assert_eq!(session.game.lives(), Some(0));
+
+    run_until(&mut session, |session| session.game.lives() != Some(0));
+    assert_eq!(session.game.lives(), Some(50));
+}
@@ -0,0 +1,45 @@
+use invade_rs::engine::geometry::{Point, Rect};
+use invade_rs::engine::mask::Mask;
+
+#[test]
+fn overlaps_only_where_pixels_are_set() {
+    let mask = Mask::from_pattern(&["....", ".##.", ".##.", "...."]);
+
+    assert_eq!(mask.count(), 4);
+    assert!(mask.overlaps(&Rect::new_from_x_y_w_h(0, 0, 2, 2)));
+    assert!(!mask.overlaps(&Rect::new_from_x_y_w_h(0, 0, 1, 4)));
+    assert!(!mask.overlaps(&Rect::new_from_x_y_w_h(-3, -3, 2, 2)));
+}
+
+#[test]
+fn hits_are_found_from_the_requested_side() {
+    let mask = Mask::from_pattern(&["#...", "....", "..#.", "..#."]);
+    let column = Rect::new_from_x_y_w_h(2, 0, 1, 4);
+
+    assert_eq!(mask.topmost_hit(&column), Some(Point { x: 2, y: 2 }));
+    assert_eq!(mask.bottommost_hit(&column), Some(Point { x: 2, y: 3 }));
+}
+
+#[test]
+fn erase_stamps_a_crater_and_clips_at_the_edges() {
+    let mut mask = Mask::from_fn(4, 4, |_, _| true);
+    let crater = Mask::from_pattern(&["##", "#."]);
+
+    mask.erase(&crater, &Point { x: 3, y: 3 });
+    mask.erase(&crater, &Point { x: 0, y: 0 });
+
+    assert!(!mask.get(3, 3));
+    assert!(!mask.get(0, 0) && !mask.get(1, 0) && !mask.get(0, 1));
+    assert!(mask.get(1, 1));
+    assert_eq!(mask.count(), 12);
+}
+
+#[test]
+fn to_bitmap_paints_set_pixels() {
+    let mask = Mask::from_pattern(&["#.", ".#"]);
+    let bitmap = mask.to_bitmap([0xff, 0, 0, 0xff]);
+
+    assert_eq!(bitmap.pixel(0, 0), Some([0xff, 0, 0, 0xff]));
+    assert_eq!(bitmap.pixel(1, 0), Some([0, 0, 0, 0]));
+    assert_eq!(Mask::from_bitmap(&bitmap), mask);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use std::rc::Rc;
+
+use invade_rs::engine::event::{Event, Pointer, PointerButton, PointerKind};
+use invade_rs::engine::geometry::Point;
+use invade_rs::engine::settings::{MemorySettings, SettingsStore};
+
+use common::Session;
+
+fn mouse(x: i16, button: Option<PointerButton>) -> Pointer {
+    Pointer {
+        id: 1,
+        kind: PointerKind::Mouse,
+        position: Point { x, y: 300 },
+        button,
+    }
+}
+
+fn start(scheme: &str) -> Session {
+    let settings = MemorySettings::new();
+    settings
+        .save("invade-rs.control-scheme", &format!("\"{}\"", scheme))
+        .unwrap();
+    Session::start_with(|game| game.with_settings(Rc::new(settings)))
+}
+
+#[test]
+fn ship_follows_the_mouse_at_its_normal_speed() {
+    let mut session = start("Mouse");
+    let origin = session.ship_x();
+
+    session.update(&[Event::PointerMove(mouse(100, None))]);
+    session.run(29);
+    // Two pixels a frame, the same as holding an arrow key.
+    assert_eq!(origin - session.ship_x(), 60);
+
+    session.run(120);
+    assert!(session.ship_x().abs_diff(100) <= 1);
+}
+
+#[test]
+fn keyboard_scheme_ignores_the_mouse() {
+    let mut session = start("Keyboard");
+    let origin = session.ship_x();
+
+    session.update(&[Event::PointerDown(mouse(100, Some(PointerButton::Primary)))]);
+    session.run(30);
+
+    assert_eq!(session.ship_x(), origin);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use invade_rs::engine::Game;
+
+use common::{DrawLog, Session};
+
+fn score(session: &Session) -> u32 {
+    session.game.score().unwrap().current()
+}
+
+#[test]
+fn pausing_freezes_the_game_until_resumed() {
+    let mut session = Session::start();
+    session.tap(&["Space"]);
+    session.run(5);
+    let before = session.render();
+    let sprites = DrawLog::of(&session.game.draw(1.0));
+
+    // Long enough for the missile in flight to have hit the fleet.
+    session.tap(&["Escape"]);
+    for _ in 0..600 {
+        session.update(&[]);
+        assert!(DrawLog::of(&session.game.draw(1.0)).contains(&sprites));
+    }
+    assert_eq!(score(&session), 0);
+
+    session.tap(&["Escape"]);
+    assert!(session.render() == before);
+    for _ in 0..300 {
+        session.update(&[]);
+        if 0 < score(&session) {
+            return;
+        }
+    }
+    panic!("the missile never moved on after resuming");
+}
+
+#[test]
+fn quit_waits_on_the_title_until_fire() {
+    let mut session = Session::start();
+
+    session.tap(&["Escape"]);
+    session.tap(&["ArrowUp", "Enter"]);
+    let title = session.render();
+    session.run(120);
+    assert!(session.render() == title);
+
+    session.tap(&["Space"]);
+    session.run(120);
+    assert!(session.render() != title);
+}
@@ -0,0 +1,35 @@
+use invade_rs::engine::random::Rng;
+
+#[test]
+fn same_seed_produces_same_sequence() {
+    let mut a = Rng::new(42);
+    let mut b = Rng::new(42);
+
+    let a: Vec<f32> = (0..16).map(|_| a.next_f32()).collect();
+    let b: Vec<f32> = (0..16).map(|_| b.next_f32()).collect();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn different_seeds_diverge() {
+    let mut a = Rng::new(1);
+    let mut b = Rng::new(2);
+
+    assert_ne!(a.next_u64(), b.next_u64());
+    assert_eq!(a.seed(), 1);
+}
+
+#[test]
+fn sequence_is_pinned_for_a_fixed_seed() {
+    let mut rng = Rng::new(42);
+
+    let values: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+    assert_eq!(
+        values,
+        vec![
+            12578764544318200737,
+            17529487244874322312,
+            7886285670807131020
+        ]
+    );
+}
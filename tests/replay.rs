@@ -0,0 +1,131 @@
+mod common;
+
+use futures::executor::block_on;
+
+use invade_rs::engine::clock::ManualClock;
+use invade_rs::engine::event::Event;
+use invade_rs::engine::replay::{RecordingEventSource, Replay, ReplayEventSource};
+use invade_rs::engine::{FixedTimestep, GameLoop};
+use invade_rs::invade_rs::config::{Config, ShieldStyle};
+
+use common::{CountingRenderer, QueuedEvents, RecordingGame};
+
+fn record() -> (Replay, Vec<Vec<Event>>) {
+    let game = RecordingGame::default();
+    let updates = game.events.clone();
+    let events = QueuedEvents::default();
+    let queue = events.0.clone();
+    let recorder = RecordingEventSource::new(events, Some(7));
+    let replay = recorder.replay();
+
+    let mut game_loop = GameLoop::new(game, CountingRenderer::default(), recorder)
+        .with_fixed_timestep(FixedTimestep::new(100.0, 5));
+    block_on(game_loop.initialize(&ManualClock::new(0.0))).unwrap();
+
+    queue
+        .borrow_mut()
+        .push_back(Event::KeyDown("ArrowLeft".to_string()));
+    game_loop.step(25.0).unwrap();
+    queue
+        .borrow_mut()
+        .push_back(Event::KeyUp("ArrowLeft".to_string()));
+    queue
+        .borrow_mut()
+        .push_back(Event::KeyDown("Space".to_string()));
+    game_loop.step(40.0).unwrap();
+
+    let replay = replay.borrow().clone();
+    let updates = updates.borrow().clone();
+    (replay, updates)
+}
+
+#[test]
+fn recording_tags_events_with_their_frame() {
+    let (replay, _) = record();
+
+    let frames: Vec<u64> = replay.events.iter().map(|e| e.frame).collect();
+    assert_eq!(frames, vec![0, 2, 2]);
+    assert_eq!(replay.events[1].timestamp, 20.0);
+    assert_eq!(replay.seed, Some(7));
+}
+
+#[test]
+fn replay_round_trips_through_json_and_bytes() {
+    let (replay, _) = record();
+
+    assert_eq!(
+        Replay::from_json(&replay.to_json().unwrap()).unwrap(),
+        replay
+    );
+    assert_eq!(
+        Replay::from_bytes(&replay.to_bytes().unwrap()).unwrap(),
+        replay
+    );
+}
+
+#[test]
+fn replay_feeds_events_back_on_the_same_frames() {
+    let (replay, recorded_updates) = record();
+
+    let game = RecordingGame::default();
+    let updates = game.events.clone();
+    let mut game_loop = GameLoop::new(
+        game,
+        CountingRenderer::default(),
+        ReplayEventSource::new(replay),
+    )
+    .with_fixed_timestep(FixedTimestep::new(100.0, 5));
+    block_on(game_loop.initialize(&ManualClock::new(0.0))).unwrap();
+
+    // Different frame pacing must not change which tick sees which event.
+    for timestamp in [10.0, 12.0, 30.0, 40.0] {
+        game_loop.step(timestamp).unwrap();
+    }
+
+    assert_eq!(*updates.borrow(), recorded_updates);
+}
+
+#[test]
+fn replay_reproduces_variable_frame_times() {
+    let game = RecordingGame::default();
+    let recorded_deltas = game.deltas.clone();
+    let recorder = RecordingEventSource::new(QueuedEvents::default(), None);
+    let replay = recorder.replay();
+    let mut game_loop = GameLoop::new(game, CountingRenderer::default(), recorder);
+    block_on(game_loop.initialize(&ManualClock::new(0.0))).unwrap();
+    for timestamp in [16.0, 50.0, 58.0] {
+        game_loop.step(timestamp).unwrap();
+    }
+
+    let game = RecordingGame::default();
+    let deltas = game.deltas.clone();
+    let replay = replay.borrow().clone();
+    let mut game_loop = GameLoop::new(
+        game,
+        CountingRenderer::default(),
+        ReplayEventSource::new(replay),
+    );
+    block_on(game_loop.initialize(&ManualClock::new(0.0))).unwrap();
+    for timestamp in [16.0, 32.0, 48.0] {
+        game_loop.step(timestamp).unwrap();
+    }
+
+    assert_eq!(*deltas.borrow(), vec![16.0, 34.0, 8.0]);
+    assert_eq!(*deltas.borrow(), *recorded_deltas.borrow());
+}
+
+#[test]
+fn replay_carries_the_game_config() {
+    let config = Config {
+        shield_style: ShieldStyle::Mask,
+        ..Config::default()
+    };
+    let recorder = RecordingEventSource::new(QueuedEvents::default(), Some(7))
+        .with_config(config.to_json().unwrap());
+
+    let json = recorder.replay().borrow().to_json().unwrap();
+    let replay = Replay::from_json(&json).unwrap();
+
+    assert_eq!(replay.version, Replay::VERSION);
+    assert_eq!(Config::from_json(&replay.config.unwrap()).unwrap(), config);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use invade_rs::engine::event::Event;
+use invade_rs::engine::font::{Align, BitmapFont};
+use invade_rs::engine::geometry::Point;
+use invade_rs::engine::random::Rng;
+use invade_rs::engine::Game;
+use invade_rs::invade_rs::ferris::FerrisColor;
+use invade_rs::invade_rs::score::Score;
+use invade_rs::invade_rs::turbo_fish::TurboFish;
+
+use common::{DrawLog, Session};
+
+fn shoot_an_enemy(session: &mut Session) -> u32 {
+    session.update(&[Event::KeyDown("Space".to_string())]);
+    session.update(&[Event::KeyUp("Space".to_string())]);
+    for _ in 0..300 {
+        session.update(&[]);
+        let score = session.game.score().unwrap();
+        if score.current() > 0 {
+            return score.current();
+        }
+    }
+    panic!("the missile never hit");
+}
+
+#[test]
+fn the_high_score_follows_the_best_game() {
+    let mut score = Score::new(50);
+
+    score.add(30);
+    assert_eq!((score.current(), score.high()), (30, 50));
+    score.add(40);
+    assert_eq!((score.current(), score.high()), (70, 70));
+}
+
+#[test]
+fn ferris_rows_are_worth_more_the_higher_they_fly() {
+    assert_eq!(FerrisColor::Blue.points(), 10);
+    assert_eq!(FerrisColor::Green.points(), 20);
+    assert_eq!(FerrisColor::Magenta.points(), 30);
+}
+
+#[test]
+fn turbo_fish_mystery_points_are_drawn_from_the_seeded_rng() {
+    let sprite_sheet = Rc::new(common::sprite_sheet());
+    let points = |seed| {
+        let mut rng = Rng::new(seed);
+        TurboFish::new(sprite_sheet.clone(), Point::default(), &mut rng).points()
+    };
+
+    assert_eq!(points(7), points(7));
+    let values: HashSet<u32> = (0..100).map(points).collect();
+    assert_eq!(values, HashSet::from([50, 100, 150, 300]));
+}
+
+#[test]
+fn the_high_score_is_kept_when_the_game_restarts() {
+    let mut session = Session::start();
+    let points = shoot_an_enemy(&mut session);
+
+    session.tap(&["Escape"]);
+    session.tap(&["ArrowDown"]);
+    session.tap(&["ArrowDown"]);
+    session.tap(&["Enter"]);
+
+    let score = session.game.score().unwrap();
+    assert_eq!((score.current(), score.high()), (0, points));
+}
+
+#[test]
+fn the_hud_draws_the_score_and_high_score() {
+    let mut session = Session::start();
+    let points = shoot_an_enemy(&mut session);
+
+    let drawn = DrawLog::of(&session.game.draw(1.0));
+
+    let font = BitmapFont::new(Rc::new(common::sprite_sheet()));
+    let text = format!("{:05}", points);
+    let expected = DrawLog::of(&[
+        font.draw(&text, &Point { x: 20, y: 28 }, Align::Left, 0),
+        font.draw(&text, &Point { x: 580, y: 28 }, Align::Right, 0),
+    ]);
+    assert!(drawn.contains(&expected));
+}
@@ -0,0 +1,82 @@
+use invade_rs::engine::sequence::{Frame, PlaybackMode, Sequence};
+
+fn frames(names: &[&str]) -> Vec<Frame> {
+    names
+        .iter()
+        .map(|name| Frame::new(name.to_string(), 100.0))
+        .collect()
+}
+
+fn play(sequence: &mut Sequence, steps: usize) -> Vec<String> {
+    (0..steps)
+        .map(|_| {
+            sequence.update(100.0);
+            sequence.current_frame_cell_name().to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn long_deltas_skip_whole_frames() {
+    let mut sequence = Sequence::new(frames(&["a", "b", "c"]));
+
+    sequence.update(250.0);
+    assert_eq!(sequence.current_frame_cell_name(), "c");
+    sequence.update(100.0);
+    assert_eq!(sequence.current_frame_cell_name(), "a");
+}
+
+#[test]
+fn once_stops_on_the_last_frame() {
+    let mut sequence = Sequence::new(frames(&["a", "b"])).with_mode(PlaybackMode::Once);
+
+    sequence.update(150.0);
+    assert!(!sequence.finished());
+    sequence.update(1000.0);
+    assert!(sequence.finished());
+    assert_eq!(sequence.current_frame_cell_name(), "b");
+}
+
+#[test]
+fn ping_pong_bounces_between_the_ends() {
+    let mut sequence = Sequence::new(frames(&["a", "b", "c"])).with_mode(PlaybackMode::PingPong);
+
+    sequence.update(50.0);
+    assert_eq!(play(&mut sequence, 5), ["b", "c", "b", "a", "b"]);
+}
+
+#[test]
+fn loop_times_finishes_after_the_requested_loops() {
+    let mut sequence = Sequence::new(frames(&["a", "b"])).with_mode(PlaybackMode::LoopTimes(2));
+
+    sequence.update(50.0);
+    assert_eq!(play(&mut sequence, 3), ["b", "a", "b"]);
+    assert!(!sequence.finished());
+    sequence.update(100.0);
+    assert!(sequence.finished());
+}
+
+#[test]
+fn markers_fire_when_their_frame_is_entered() {
+    let frames = vec![
+        Frame::new("a".to_string(), 100.0).with_marker("start"),
+        Frame::new("b".to_string(), 100.0),
+        Frame::new("c".to_string(), 100.0).with_marker("hit"),
+    ];
+    let mut sequence = Sequence::new(frames);
+
+    sequence.update(10.0);
+    assert_eq!(sequence.fired_markers(), ["start"]);
+    sequence.update(10.0);
+    assert!(sequence.fired_markers().is_empty());
+    sequence.update(300.0);
+    assert_eq!(sequence.fired_markers(), ["hit", "start"]);
+}
+
+#[test]
+fn speed_scales_playback() {
+    let mut sequence = Sequence::new(frames(&["a", "b", "c"])).with_speed(2.0);
+
+    sequence.update(75.0);
+    assert_eq!(sequence.current_frame_cell_name(), "b");
+}
@@ -0,0 +1,84 @@
+use invade_rs::engine::audio::sequencer::StepSequencer;
+use invade_rs::engine::audio::sfx::PcmBuffer;
+use invade_rs::engine::audio::{AudioBackend, AudioCall, RecordingAudioBackend, Sound};
+
+const STEP: f32 = 100.0;
+
+fn setup() -> (RecordingAudioBackend, Vec<Sound>) {
+    let audio = RecordingAudioBackend::new();
+    let pattern = (0..4)
+        .map(|_| audio.upload(&PcmBuffer::new(8000, vec![0.0])).unwrap())
+        .collect();
+    (audio, pattern)
+}
+
+fn played(audio: &RecordingAudioBackend) -> Vec<Sound> {
+    audio
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            AudioCall::Play(sound) => Some(sound),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn first_step_plays_immediately_and_pattern_loops() {
+    let (audio, pattern) = setup();
+    let mut sequencer = StepSequencer::new(pattern.clone(), STEP);
+
+    sequencer.update(0.0, &audio).unwrap();
+    assert_eq!(played(&audio), vec![pattern[0]]);
+
+    for _ in 0..8 {
+        sequencer.update(50.0, &audio).unwrap();
+    }
+    assert_eq!(
+        played(&audio),
+        vec![pattern[0], pattern[1], pattern[2], pattern[3], pattern[0]]
+    );
+}
+
+#[test]
+fn tempo_scales_the_step_rate() {
+    let (audio, pattern) = setup();
+    let mut sequencer = StepSequencer::new(pattern, STEP);
+    sequencer.set_tempo(2.0);
+
+    sequencer.update(0.0, &audio).unwrap();
+    for _ in 0..10 {
+        sequencer.update(20.0, &audio).unwrap();
+    }
+
+    assert_eq!(played(&audio).len(), 1 + 4);
+    assert_eq!(sequencer.next_step(), 1);
+}
+
+#[test]
+fn paused_sequencer_keeps_its_place() {
+    let (audio, pattern) = setup();
+    let mut sequencer = StepSequencer::new(pattern.clone(), STEP);
+    sequencer.update(0.0, &audio).unwrap();
+
+    sequencer.pause();
+    sequencer.update(1000.0, &audio).unwrap();
+    assert_eq!(played(&audio).len(), 1);
+
+    sequencer.resume();
+    sequencer.update(STEP, &audio).unwrap();
+    assert_eq!(played(&audio), vec![pattern[0], pattern[1]]);
+}
+
+#[test]
+fn reset_restarts_from_the_first_step() {
+    let (audio, pattern) = setup();
+    let mut sequencer = StepSequencer::new(pattern.clone(), STEP);
+    sequencer.update(STEP * 2.0, &audio).unwrap();
+
+    sequencer.reset();
+    sequencer.update(0.0, &audio).unwrap();
+
+    assert_eq!(played(&audio).last(), Some(&pattern[0]));
+    assert_eq!(sequencer.next_step(), 1);
+}
@@ -0,0 +1,82 @@
+use invade_rs::engine::audio::sfx::{Envelope, SfxParams, Waveform};
+
+const SAMPLE_RATE: u32 = 8000;
+
+fn zero_crossings(samples: &[f32]) -> usize {
+    samples
+        .windows(2)
+        .filter(|pair| (pair[0] < 0.0) != (pair[1] < 0.0))
+        .count()
+}
+
+#[test]
+fn length_follows_the_envelope() {
+    let params = SfxParams {
+        envelope: Envelope {
+            attack: 0.1,
+            sustain: 0.2,
+            decay: 0.2,
+        },
+        ..SfxParams::default()
+    };
+
+    let buffer = params.synthesize(SAMPLE_RATE);
+
+    assert_eq!(buffer.sample_rate(), SAMPLE_RATE);
+    assert_eq!(buffer.samples().len(), 4000);
+    assert!((buffer.peak() - params.volume).abs() < 1e-3);
+    assert!(buffer.samples()[0].abs() < 1e-3);
+}
+
+#[test]
+fn square_wave_only_takes_two_levels_while_sustained() {
+    let params = SfxParams {
+        envelope: Envelope {
+            attack: 0.0,
+            sustain: 0.1,
+            decay: 0.0,
+        },
+        volume: 0.5,
+        ..SfxParams::default()
+    };
+
+    let buffer = params.synthesize(SAMPLE_RATE);
+
+    assert!(buffer
+        .samples()
+        .iter()
+        .all(|sample| *sample == 0.5 || *sample == -0.5));
+    assert_eq!(zero_crossings(buffer.samples()), 88);
+}
+
+#[test]
+fn downward_slide_lowers_the_pitch() {
+    let steady = SfxParams {
+        waveform: Waveform::Sawtooth,
+        frequency: 800.0,
+        ..SfxParams::default()
+    };
+    let sliding = SfxParams {
+        frequency_slide: -3000.0,
+        ..steady
+    };
+
+    let steady = steady.synthesize(SAMPLE_RATE);
+    let sliding = sliding.synthesize(SAMPLE_RATE);
+
+    assert!(zero_crossings(sliding.samples()) < zero_crossings(steady.samples()));
+}
+
+#[test]
+fn noise_is_deterministic() {
+    let params = SfxParams {
+        waveform: Waveform::Noise,
+        ..SfxParams::default()
+    };
+
+    assert_eq!(
+        params.synthesize(SAMPLE_RATE),
+        params.synthesize(SAMPLE_RATE)
+    );
+    assert!(0.0 < params.synthesize(SAMPLE_RATE).peak());
+}
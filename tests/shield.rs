@@ -0,0 +1,98 @@
+mod common;
+
+use std::rc::Rc;
+
+use invade_rs::engine::geometry::Point;
+use invade_rs::invade_rs::shield::{create_shield, ShieldElement};
+
+use common::DrawLog;
+
+const LEFT_CORNER: usize = 0;
+const RIGHT_CORNER: usize = 3;
+const MIDDLE: usize = 5;
+
+fn element(index: usize) -> ShieldElement {
+    create_shield(Rc::new(common::sprite_sheet()), &Point::default()).swap_remove(index)
+}
+
+// The name of the shield cell the element draws itself with.
+fn drawn_cell(element: &ShieldElement) -> String {
+    let sprite_sheet = common::sprite_sheet();
+    let source = |log: DrawLog| log.0.borrow()[0].0;
+    let drawn = source(DrawLog::of(&[element.draw(1.0).unwrap()]));
+
+    (0..6)
+        .map(|n| format!("shield_red_{}.png", n))
+        .find(|name| {
+            let log = DrawLog::default();
+            let cell = sprite_sheet.cell(name).unwrap();
+            sprite_sheet.draw(&log, cell, &Point::default());
+            source(log) == drawn
+        })
+        .expect("not a shield cell")
+}
+
+// The cells an element steps through when hit again and again for `damage`,
+// up to the hit that wears it out.
+fn cells_under_fire(mut element: ShieldElement, damage: u32) -> Vec<String> {
+    let mut cells = vec![drawn_cell(&element)];
+    loop {
+        element.damage(damage);
+        if element.is_worn_out() {
+            return cells;
+        }
+        cells.push(drawn_cell(&element));
+    }
+}
+
+#[test]
+fn every_hit_from_below_steps_through_the_next_damage_cell() {
+    assert_eq!(
+        cells_under_fire(element(MIDDLE), 1),
+        [
+            "shield_red_0.png",
+            "shield_red_1.png",
+            "shield_red_2.png",
+            "shield_red_3.png"
+        ]
+    );
+}
+
+#[test]
+fn beams_from_above_step_two_damage_cells_at_a_time() {
+    assert_eq!(
+        cells_under_fire(element(MIDDLE), 2),
+        ["shield_red_0.png", "shield_red_2.png"]
+    );
+}
+
+#[test]
+fn corners_wear_down_through_the_same_damage_cells() {
+    assert_eq!(
+        cells_under_fire(element(LEFT_CORNER), 1),
+        [
+            "shield_red_4.png",
+            "shield_red_1.png",
+            "shield_red_2.png",
+            "shield_red_3.png"
+        ]
+    );
+    assert_eq!(
+        cells_under_fire(element(RIGHT_CORNER), 1),
+        [
+            "shield_red_5.png",
+            "shield_red_1.png",
+            "shield_red_2.png",
+            "shield_red_3.png"
+        ]
+    );
+}
+
+#[test]
+fn an_element_is_worn_out_once_its_hit_points_run_out() {
+    let mut element = element(MIDDLE);
+    element.damage(3);
+    assert!(!element.is_worn_out());
+    element.damage(1);
+    assert!(element.is_worn_out());
+}
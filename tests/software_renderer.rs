@@ -0,0 +1,79 @@
+mod common;
+
+use std::path::Path;
+
+use futures::executor::block_on;
+
+use invade_rs::engine::clock::ManualClock;
+use invade_rs::engine::geometry::Rect;
+use invade_rs::engine::image::{Bitmap, Image};
+use invade_rs::engine::renderer::{Renderer, SoftwareRenderer};
+use invade_rs::engine::{FixedTimestep, GameLoop};
+use invade_rs::invade_rs::InvadeRs;
+
+use common::QueuedEvents;
+
+const RED: [u8; 4] = [0xff, 0, 0, 0xff];
+const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+#[test]
+fn clear_fills_rect_with_black() {
+    let renderer = SoftwareRenderer::new(4, 4);
+    renderer.clear(&Rect::new_from_x_y_w_h(1, 1, 2, 2));
+
+    let bitmap = renderer.bitmap();
+    assert_eq!(bitmap.pixel(0, 0), Some(CLEAR));
+    assert_eq!(bitmap.pixel(1, 1), Some([0, 0, 0, 0xff]));
+    assert_eq!(bitmap.pixel(3, 3), Some(CLEAR));
+}
+
+#[test]
+fn draw_image_scales_and_skips_transparent_pixels() {
+    let mut pixels = vec![];
+    pixels.extend_from_slice(&RED);
+    pixels.extend_from_slice(&CLEAR);
+    let image: Image = Bitmap::from_rgba(2, 1, pixels).unwrap().into();
+
+    let renderer = SoftwareRenderer::new(8, 8);
+    renderer.clear(&Rect::new_from_x_y_w_h(0, 0, 8, 8));
+    renderer.draw_image(
+        &image,
+        &Rect::new_from_x_y_w_h(0, 0, 2, 1),
+        &Rect::new_from_x_y_w_h(2, 2, 4, 2),
+    );
+
+    let bitmap = renderer.bitmap();
+    assert_eq!(bitmap.pixel(2, 2), Some(RED));
+    assert_eq!(bitmap.pixel(3, 3), Some(RED));
+    assert_eq!(bitmap.pixel(4, 2), Some([0, 0, 0, 0xff]));
+    assert_eq!(bitmap.pixel(1, 2), Some([0, 0, 0, 0xff]));
+}
+
+#[test]
+fn invade_rs_matches_golden_image() {
+    let game = InvadeRs::with_seed(1).with_sprite_sheet(common::sprite_sheet());
+    let mut game_loop = GameLoop::new(
+        game,
+        SoftwareRenderer::new(600, 600),
+        QueuedEvents::default(),
+    )
+    .with_fixed_timestep(FixedTimestep::new(60.0, 5));
+    block_on(game_loop.initialize(&ManualClock::new(0.0))).unwrap();
+    for frame in 1..=30 {
+        game_loop.step(frame as f64 * 1000.0 / 60.0).unwrap();
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let golden = root.join("tests/golden/invade_rs.png");
+    if std::env::var("UPDATE_GOLDEN").is_ok() {
+        std::fs::write(&golden, game_loop.renderer().to_png().unwrap()).unwrap();
+    }
+    assert!(
+        golden.exists(),
+        "{} is missing; rerun with UPDATE_GOLDEN=1 to create it",
+        golden.display()
+    );
+
+    let expected = Bitmap::from_png(&std::fs::read(&golden).unwrap()).unwrap();
+    assert!(*game_loop.renderer().bitmap() == expected);
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use std::rc::Rc;
+
+use invade_rs::engine::geometry::Point;
+use invade_rs::engine::input::InputState;
+use invade_rs::engine::random::Rng;
+use invade_rs::invade_rs::character::GameCharacter;
+use invade_rs::invade_rs::config::{Config, SpeedCurve};
+use invade_rs::invade_rs::ferris::{Ferris, FerrisColor};
+use invade_rs::invade_rs::fsm::{StateMachine, Transition};
+use invade_rs::invade_rs::game_state::GameStateMachine;
+
+use common::{DrawLog, FRAME};
+
+#[test]
+fn full_fleet_moves_at_base_speed() {
+    let curve = SpeedCurve::default();
+
+    assert_eq!(curve.multiplier(54, 54), 1.0);
+    assert_eq!(curve.multiplier(0, 0), 1.0);
+}
+
+#[test]
+fn speed_rises_towards_max_as_fleet_shrinks() {
+    let curve = SpeedCurve {
+        max_multiplier: 5.0,
+        exponent: 1.0,
+    };
+
+    assert_eq!(curve.multiplier(50, 100), 3.0);
+    assert!(curve.multiplier(10, 100) < curve.multiplier(1, 100));
+    assert_eq!(curve.multiplier(0, 100), 5.0);
+}
+
+// How far a lone Ferris walks in one second of 60 Hz ticks.
+fn distance_walked(wave: u32, speed_scale: f32) -> i16 {
+    let sprite_sheet = Rc::new(common::sprite_sheet());
+    let start = Point { x: 300, y: 100 };
+    let mut ferris = Ferris::new(sprite_sheet, start, FerrisColor::Blue).with_wave(wave);
+    ferris.set_speed_scale(speed_scale);
+    let mut rng = Rng::new(1);
+    for _ in 0..60 {
+        ferris.update(1000.0 / 60.0, &mut rng);
+    }
+    start.x - ferris.bounding_box().x()
+}
+
+#[test]
+fn ferris_speed_is_not_rounded_to_whole_pixels_per_tick() {
+    // The base speed is 80 pixels a second, 15% more each wave.
+    assert!((79..=81).contains(&distance_walked(1, 1.0)));
+    assert!((91..=93).contains(&distance_walked(2, 1.0)));
+    assert!((103..=105).contains(&distance_walked(3, 1.0)));
+    assert!((99..=101).contains(&distance_walked(1, 1.25)));
+}
+
+// How many times a surviving Ferris changes animation frame in three
+// seconds once all but `survivors` of the fleet are gone.
+fn frame_changes(config: Config, survivors: usize) -> usize {
+    let mut in_game = common::play(common::wave(1, config));
+    let mut kept = 0;
+    in_game.characters.retain(|c| {
+        if !matches!(&*c.borrow(), GameCharacter::Ferris(_)) {
+            return true;
+        }
+        kept += 1;
+        kept <= survivors
+    });
+    let ferris = in_game
+        .characters
+        .iter()
+        .find(|c| matches!(&*c.borrow(), GameCharacter::Ferris(_)))
+        .unwrap()
+        .clone();
+    let cell = || {
+        let log = DrawLog::of(&[ferris.borrow().draw(1.0).unwrap()]);
+        let (source, _) = log.0.borrow()[0];
+        source
+    };
+
+    let mut state = GameStateMachine::InGame(in_game);
+    let mut last = cell();
+    let mut changes = 0;
+    for _ in 0..(3000.0 / FRAME) as usize {
+        match state.update(FRAME, &InputState::new(), &[]) {
+            Transition::Stay(next) => state = next,
+            _ => panic!("unexpected transition"),
+        }
+        if cell() != last {
+            last = cell();
+            changes += 1;
+        }
+    }
+    changes
+}
+
+#[test]
+fn ferris_animate_faster_along_the_speed_curve_as_the_fleet_shrinks() {
+    let curve = SpeedCurve {
+        max_multiplier: 3.0,
+        exponent: 1.0,
+    };
+    let config = Config {
+        fleet_speed_curve: curve,
+        ..Config::default()
+    };
+    let fleet_size = 54;
+
+    // Each walk frame lasts 150ms at the base speed.
+    for survivors in [54, 27, 9] {
+        let expected = 3000.0 / 150.0 * curve.multiplier(survivors, fleet_size);
+        let changes = frame_changes(config, survivors) as f32;
+        assert!(
+            (changes - expected).abs() < 1.5,
+            "{} != {}",
+            changes,
+            expected
+        );
+    }
+}
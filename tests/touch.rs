@@ -0,0 +1,58 @@
+mod common;
+
+use invade_rs::engine::audio::AudioCall;
+use invade_rs::engine::event::{CanvasBounds, Event, Pointer, PointerButton, PointerKind};
+use invade_rs::engine::geometry::Point;
+
+use common::Session;
+
+fn touch(id: i32, x: i16, y: i16) -> Pointer {
+    Pointer {
+        id,
+        kind: PointerKind::Touch,
+        position: Point { x, y },
+        button: Some(PointerButton::Primary),
+    }
+}
+
+#[test]
+fn client_coordinates_are_scaled_to_the_canvas() {
+    // A 600x600 canvas shrunk to 300x300 CSS pixels, 10px from the page edge.
+    let bounds = CanvasBounds {
+        left: 10.0,
+        top: 20.0,
+        width: 300.0,
+        height: 300.0,
+        canvas_width: 600,
+        canvas_height: 600,
+    };
+
+    assert_eq!(bounds.to_canvas_space(10.0, 20.0), Point { x: 0, y: 0 });
+    assert_eq!(
+        bounds.to_canvas_space(160.0, 95.5),
+        Point { x: 300, y: 151 }
+    );
+}
+
+#[test]
+fn fire_button_fires_once_per_touch() {
+    let mut session = Session::start();
+    let plays = |session: &Session| {
+        session
+            .audio
+            .calls()
+            .iter()
+            .filter(|call| matches!(call, AudioCall::Play(_)))
+            .count()
+    };
+    let before = plays(&session);
+
+    session.update(&[Event::PointerDown(touch(1, 500, 520))]);
+    // A second finger on the move zones and a wobble of the first one.
+    session.update(&[
+        Event::PointerDown(touch(2, 60, 520)),
+        Event::PointerMove(touch(1, 510, 530)),
+    ]);
+
+    assert_eq!(plays(&session), before + 1);
+}
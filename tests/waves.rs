@@ -0,0 +1,139 @@
+mod common;
+
+use std::rc::Rc;
+
+use invade_rs::engine::font::{Align, BitmapFont};
+use invade_rs::engine::geometry::Point;
+use invade_rs::engine::input::InputState;
+use invade_rs::invade_rs::character::GameCharacter;
+use invade_rs::invade_rs::config::Config;
+use invade_rs::invade_rs::fsm::{StateMachine, Transition};
+use invade_rs::invade_rs::game_state::in_game::InGame;
+use invade_rs::invade_rs::game_state::GameStateMachine;
+
+use common::{play, run_until_replaced, wave, DrawLog, FRAME};
+
+fn is_ferris(c: &GameCharacter) -> bool {
+    matches!(c, GameCharacter::Ferris(_))
+}
+
+fn fleet_top(in_game: &InGame) -> i16 {
+    in_game
+        .characters
+        .iter()
+        .filter(|c| is_ferris(&c.borrow()))
+        .map(|c| c.borrow().bounding_box().top())
+        .min()
+        .unwrap()
+}
+
+fn shield_count(in_game: &InGame) -> usize {
+    in_game
+        .characters
+        .iter()
+        .filter(|c| c.borrow().is_shield())
+        .count()
+}
+
+// Knocks out `shields` shield elements and the whole fleet, then plays on
+// into the next wave.
+fn clear_wave(mut in_game: InGame, shields: usize) -> InGame {
+    let mut knocked_out = 0;
+    in_game.characters.retain(|c| {
+        let c = c.borrow();
+        if c.is_shield() && knocked_out < shields {
+            knocked_out += 1;
+            return false;
+        }
+        !is_ferris(&c)
+    });
+    match run_until_replaced(GameStateMachine::InGame(in_game)) {
+        (GameStateMachine::OutGame(next_wave), 1) => play(next_wave),
+        _ => panic!("the cleared wave did not end at once"),
+    }
+}
+
+#[test]
+fn later_waves_open_with_a_banner() {
+    let (_, frames) = run_until_replaced(GameStateMachine::OutGame(wave(1, Config::default())));
+    assert_eq!(frames, 1);
+
+    let second = wave(2, Config::default());
+    let font = BitmapFont::new(Rc::new(common::sprite_sheet())).with_scale(2);
+    let banner = DrawLog::of(&[font.draw("WAVE 2", &Point { x: 300, y: 400 }, Align::Center, 0)]);
+    assert!(DrawLog::of(&second.draw(1.0)).contains(&banner));
+
+    let (_, frames) = run_until_replaced(GameStateMachine::OutGame(second));
+    assert!(2000.0 <= frames as f32 * FRAME);
+}
+
+#[test]
+fn later_waves_start_lower() {
+    let first = fleet_top(&play(wave(1, Config::default())));
+    let second = fleet_top(&play(wave(2, Config::default())));
+    let third = fleet_top(&play(wave(3, Config::default())));
+
+    assert!(first < second);
+    assert!(second < third);
+}
+
+#[test]
+fn later_waves_move_faster() {
+    // How far the fleet has moved sideways after a second.
+    let travel = |wave_number| {
+        let in_game = play(wave(wave_number, Config::default()));
+        let x = |in_game: &InGame| {
+            in_game
+                .characters
+                .iter()
+                .find(|c| is_ferris(&c.borrow()))
+                .map(|c| c.borrow().bounding_box().x())
+                .unwrap()
+        };
+        let start = x(&in_game);
+        let mut state = GameStateMachine::InGame(in_game);
+        for _ in 0..60 {
+            match state.update(FRAME, &InputState::new(), &[]) {
+                Transition::Stay(next) => state = next,
+                _ => panic!("unexpected transition"),
+            }
+        }
+        let GameStateMachine::InGame(in_game) = state else {
+            panic!("the wave ended");
+        };
+        (x(&in_game) - start).abs()
+    };
+
+    assert!(travel(1) < travel(2));
+    assert!(travel(2) < travel(3));
+}
+
+#[test]
+fn shields_carry_their_damage_into_the_next_wave() {
+    let config = Config {
+        shield_restore_interval: 4,
+        ..Config::default()
+    };
+    let first = play(wave(1, config));
+    let intact = shield_count(&first);
+
+    let second = clear_wave(first, 3);
+    assert_eq!(shield_count(&second), intact - 3);
+    let third = clear_wave(second, 2);
+    assert_eq!(shield_count(&third), intact - 5);
+}
+
+#[test]
+fn shields_are_rebuilt_every_restore_interval() {
+    let config = Config {
+        shield_restore_interval: 2,
+        ..Config::default()
+    };
+    let first = play(wave(1, config));
+    let intact = shield_count(&first);
+
+    let second = clear_wave(first, 3);
+    assert_eq!(shield_count(&second), intact - 3);
+    let third = clear_wave(second, 2);
+    assert_eq!(shield_count(&third), intact);
+}